@@ -7,7 +7,7 @@ use anyhow::anyhow;
 use common::board::Board;
 use common::gem::Gem;
 use common::grid::Position;
-use common::json::{cmp_coordinates, Coordinate, JsonBoard};
+use common::json::{cmp_coordinates, json_stream, Coordinate, JsonBoard};
 use common::tile::{ConnectorShape, Tile};
 use serde::Deserialize;
 
@@ -20,11 +20,11 @@ pub enum ValidJson {
 
 /// Read bytes (the JSON) from the reader and Write the results (the result JSON) to the writer
 fn read_json_and_write_json(reader: impl Read, writer: &mut impl Write) -> anyhow::Result<()> {
-    let mut test_input = get_json_iter_from_reader(reader)?;
+    let mut test_input = get_json_iter_from_reader(reader);
 
     let board: Board = match test_input
         .next()
-        .ok_or_else(|| anyhow!("No valid Board JSON found"))?
+        .ok_or_else(|| anyhow!("No valid Board JSON found"))??
     {
         ValidJson::Board(board) => (
             board,
@@ -41,7 +41,7 @@ fn read_json_and_write_json(reader: impl Read, writer: &mut impl Write) -> anyho
     // Position is the tuple (usize, usize)
     let from_pos: Position = match test_input
         .next()
-        .ok_or_else(|| anyhow!("No valid Coordinate JSON found"))?
+        .ok_or_else(|| anyhow!("No valid Coordinate JSON found"))??
     {
         ValidJson::Coordinate(coord) => coord.into(),
         _ => Err(anyhow!("Coordinate was not the second JSON object sent"))?,
@@ -55,17 +55,16 @@ fn read_json_and_write_json(reader: impl Read, writer: &mut impl Write) -> anyho
     reachable_pos.sort_by(cmp_coordinates);
 
     writer.write_all(serde_json::to_string(&reachable_pos)?.as_bytes())?;
+    writer.flush()?;
 
     Ok(())
 }
 
 /// Turn the STDIN Stream into A ValidJson Stream
-fn get_json_iter_from_reader(reader: impl Read) -> anyhow::Result<impl Iterator<Item = ValidJson>> {
-    let deserializer = serde_json::Deserializer::from_reader(reader);
-    Ok(deserializer
-        .into_iter::<crate::ValidJson>()
-        .collect::<Result<Vec<_>, _>>()?
-        .into_iter())
+fn get_json_iter_from_reader(
+    reader: impl Read,
+) -> impl Iterator<Item = serde_json::Result<ValidJson>> {
+    json_stream(reader)
 }
 
 fn main() -> anyhow::Result<()> {
@@ -6,7 +6,7 @@ use anyhow::anyhow;
 use common::{
     board::Slide,
     grid::Position,
-    json::{cmp_coordinates, Coordinate, JsonDegree, JsonDirection, JsonState},
+    json::{cmp_coordinates, json_stream, Coordinate, JsonDegree, JsonDirection, JsonState},
     state::{PlayerInfo, State},
     tile::CompassDirection,
 };
@@ -22,11 +22,11 @@ pub enum ValidJson {
 }
 
 fn read_json_and_write_json(reader: impl Read, writer: &mut impl Write) -> anyhow::Result<()> {
-    let mut test_input = get_json_iter_from_reader(reader)?;
+    let mut test_input = get_json_iter_from_reader(reader);
 
     let mut state: State<PlayerInfo> = match test_input
         .next()
-        .ok_or_else(|| anyhow!("No valid State JSON found"))?
+        .ok_or_else(|| anyhow!("No valid State JSON found"))??
     {
         ValidJson::State(state) => state.try_into()?,
         _ => Err(anyhow!("State was not the first JSON object sent"))?,
@@ -35,7 +35,7 @@ fn read_json_and_write_json(reader: impl Read, writer: &mut impl Write) -> anyho
     let slide: Slide = {
         let index: usize = match test_input
             .next()
-            .ok_or_else(|| anyhow!("No valid Index JSON found"))?
+            .ok_or_else(|| anyhow!("No valid Index JSON found"))??
         {
             ValidJson::Number(index) => index,
             _ => Err(anyhow!("Index was not the second JSON object sent"))?,
@@ -43,7 +43,7 @@ fn read_json_and_write_json(reader: impl Read, writer: &mut impl Write) -> anyho
 
         let dir: CompassDirection = match test_input
             .next()
-            .ok_or_else(|| anyhow!("No valid Direction JSON found"))?
+            .ok_or_else(|| anyhow!("No valid Direction JSON found"))??
         {
             ValidJson::Direction(dir) => dir.into(),
             _ => Err(anyhow!("Direction was not the third JSON object sent"))?,
@@ -56,7 +56,7 @@ fn read_json_and_write_json(reader: impl Read, writer: &mut impl Write) -> anyho
 
     let num_rotations: usize = match test_input
         .next()
-        .ok_or_else(|| anyhow!("No valid Degree JSON found"))?
+        .ok_or_else(|| anyhow!("No valid Degree JSON found"))??
     {
         ValidJson::Number(deg) => JsonDegree(deg).try_into()?,
         x => Err(anyhow!(
@@ -78,16 +78,15 @@ fn read_json_and_write_json(reader: impl Read, writer: &mut impl Write) -> anyho
     reachable_pos.sort_by(cmp_coordinates);
 
     writer.write_all(serde_json::to_string(&reachable_pos)?.as_bytes())?;
+    writer.flush()?;
 
     Ok(())
 }
 /// Turn the STDIN Stream into A ValidJson Stream
-fn get_json_iter_from_reader(reader: impl Read) -> anyhow::Result<impl Iterator<Item = ValidJson>> {
-    let deserializer = serde_json::Deserializer::from_reader(reader);
-    Ok(deserializer
-        .into_iter::<ValidJson>()
-        .collect::<Result<Vec<_>, _>>()?
-        .into_iter())
+fn get_json_iter_from_reader(
+    reader: impl Read,
+) -> impl Iterator<Item = serde_json::Result<ValidJson>> {
+    json_stream(reader)
 }
 
 fn main() -> anyhow::Result<()> {
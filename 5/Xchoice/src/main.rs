@@ -3,7 +3,7 @@ use std::io::{stdin, stdout, Read, Write};
 use anyhow::anyhow;
 use common::{
     grid::Position,
-    json::{Coordinate, JsonState},
+    json::{json_stream, Coordinate, JsonState},
     state::{PlayerInfo, State},
 };
 use players::{
@@ -22,25 +22,25 @@ enum ValidJson {
 }
 
 /// Turn the `impl Read` into A `ValidJson` Stream
-fn get_json_iter_from_reader(reader: impl Read) -> anyhow::Result<impl Iterator<Item = ValidJson>> {
-    let deserializer = serde_json::Deserializer::from_reader(reader);
-    Ok(deserializer
-        .into_iter::<ValidJson>()
-        .collect::<Result<Vec<_>, _>>()?
-        .into_iter())
+fn get_json_iter_from_reader(
+    reader: impl Read,
+) -> impl Iterator<Item = serde_json::Result<ValidJson>> {
+    json_stream(reader)
 }
 
-/// Writes the `impl Serialize` to the `impl Write`
+/// Writes all of the `impl Serialize` to the `impl Write` and flushes it
 fn write_json_out_to_writer(output: impl Serialize, writer: &mut impl Write) -> anyhow::Result<()> {
-    Ok(writer.write_all(serde_json::to_string(&output)?.as_bytes())?)
+    writer.write_all(serde_json::to_string(&output)?.as_bytes())?;
+    writer.flush()?;
+    Ok(())
 }
 
 fn read_and_write_json(reader: impl Read, writer: &mut impl Write) -> anyhow::Result<()> {
-    let mut input = get_json_iter_from_reader(reader)?;
+    let mut input = get_json_iter_from_reader(reader);
 
     let strat: NaiveStrategy = match input
         .next()
-        .ok_or_else(|| anyhow!("No valid JSON Strategy found"))?
+        .ok_or_else(|| anyhow!("No valid JSON Strategy found"))??
     {
         ValidJson::StrategyDesig(strat) => strat.into(),
         _ => Err(anyhow!(
@@ -50,7 +50,7 @@ fn read_and_write_json(reader: impl Read, writer: &mut impl Write) -> anyhow::Re
 
     let state: State<PlayerInfo> = match input
         .next()
-        .ok_or_else(|| anyhow!("No valid State JSON found"))?
+        .ok_or_else(|| anyhow!("No valid State JSON found"))??
     {
         ValidJson::State(state) => state.try_into()?,
         _ => Err(anyhow!("State was not the second json input found"))?,
@@ -58,7 +58,7 @@ fn read_and_write_json(reader: impl Read, writer: &mut impl Write) -> anyhow::Re
 
     let goal: Position = match input
         .next()
-        .ok_or_else(|| anyhow!("No valid State JSON found"))?
+        .ok_or_else(|| anyhow!("No valid State JSON found"))??
     {
         ValidJson::Goal(state) => state.into(),
         _ => Err(anyhow!("State was not the second json input found"))?,
@@ -85,6 +85,39 @@ mod tests {
     use std::io::BufReader;
     use std::path::Path;
 
+    /// A writer that accepts at most 3 bytes per call to `write`
+    #[derive(Default)]
+    struct ShortWriter {
+        written: Vec<u8>,
+        flushed: bool,
+    }
+
+    impl Write for ShortWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let len = buf.len().min(3);
+            self.written.extend_from_slice(&buf[..len]);
+            self.flushed = false;
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.flushed = true;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_write_json_out_to_writer_short_writes() {
+        let output = vec![Coordinate::from((1, 2)), Coordinate::from((3, 4))];
+        let mut writer = ShortWriter::default();
+        write_json_out_to_writer(&output, &mut writer).unwrap();
+        assert_eq!(
+            String::from_utf8(writer.written).unwrap(),
+            serde_json::to_string(&output).unwrap()
+        );
+        assert!(writer.flushed);
+    }
+
     #[test]
     fn test_handle_client_from_file() {
         let tests_path = Path::new("./../Tests/");
@@ -3,7 +3,7 @@ use std::io::{Read, Write};
 use anyhow::anyhow;
 use common::{
     grid::Position,
-    json::Name,
+    json::{json_stream, Name},
     state::{FullPlayerInfo, State},
 };
 use players::player::{LocalPlayer, PlayerApi};
@@ -23,67 +23,78 @@ enum ValidJson {
 }
 
 /// Turn the `impl Read` into A `ValidJson` Stream
-fn get_json_iter_from_reader(reader: impl Read) -> anyhow::Result<impl Iterator<Item = ValidJson>> {
-    let deserializer = serde_json::Deserializer::from_reader(reader);
-    Ok(deserializer
-        .into_iter::<ValidJson>()
-        .collect::<Result<Vec<_>, _>>()?
-        .into_iter())
+fn get_json_iter_from_reader(
+    reader: impl Read,
+) -> impl Iterator<Item = serde_json::Result<ValidJson>> {
+    json_stream(reader)
 }
 
-/// Writes the `impl Serialize` to the `impl Write`
+/// Writes all of the `impl Serialize` to the `impl Write` and flushes it
 fn write_json_out_to_writer(output: impl Serialize, writer: &mut impl Write) -> anyhow::Result<()> {
     writer.write_all(serde_json::to_string(&output)?.as_bytes())?;
+    writer.flush()?;
     Ok(())
 }
 
+/// Runs one game for every pair of `PlayerSpec` array and `RefereeState` read from `reader`, until
+/// `reader` runs out. The sorted names of each game's winners are written to `writer` as a JSON
+/// array, one line per game, in the order the games were read.
 pub fn read_and_write_json(
     reader: impl Read,
     writer: &mut impl Write,
     mut observers: Vec<Box<dyn Observer>>,
 ) -> anyhow::Result<()> {
-    let mut input = get_json_iter_from_reader(reader)?;
+    let mut input = get_json_iter_from_reader(reader);
+    let mut games_played = 0;
+
+    while let Some(spec) = input.next() {
+        let players: Vec<Box<dyn PlayerApi + Send>> = match spec? {
+            ValidJson::PlayerSpec(pss) => pss
+                .into_iter()
+                .map(|pss| -> Box<dyn PlayerApi + Send> {
+                    let (name, strategy) = pss.into();
+                    Box::new(LocalPlayer::new(name, strategy))
+                })
+                .collect(),
+            _ => Err(anyhow!("Recieved something other than a player spec array"))?,
+        };
 
-    let players: Vec<Box<dyn PlayerApi + Send>> = match input
-        .next()
-        .ok_or_else(|| anyhow!("Did not recieve a PlayerSpec array"))?
-    {
-        ValidJson::PlayerSpec(pss) => pss
-            .into_iter()
-            .map(|pss| -> Box<dyn PlayerApi + Send> {
-                let (name, strategy) = pss.into();
-                Box::new(LocalPlayer::new(name, strategy))
-            })
-            .collect(),
-        _ => Err(anyhow!("Recieved something other than a player spec array"))?,
-    };
+        let (state, goals): (State<FullPlayerInfo>, Vec<Position>) = match input
+            .next()
+            .ok_or_else(|| anyhow!("Didn't receive a State"))??
+        {
+            ValidJson::RefereeState(a) => a.try_into()?,
+            _ => Err(anyhow!("Recieved something other than a RefereeState"))?,
+        };
 
-    let (state, goals): (State<FullPlayerInfo>, Vec<Position>) = match input
-        .next()
-        .ok_or_else(|| anyhow!("Didn't receive a State"))?
-    {
-        ValidJson::RefereeState(a) => a.try_into()?,
-        _ => Err(anyhow!("Recieved something other than a RefereeState"))?,
-    };
+        let mut state: State<Player> = State {
+            board: state.board,
+            player_info: state
+                .player_info
+                .into_iter()
+                .zip(players)
+                .map(|(info, api)| Player::new(api, info))
+                .collect(),
+            previous_slide: state.previous_slide,
+        };
 
-    let mut state: State<Player> = State {
-        board: state.board,
-        player_info: state
-            .player_info
-            .into_iter()
-            .zip(players)
-            .map(|(info, api)| Player::new(api, info))
-            .collect(),
-        previous_slide: state.previous_slide,
-    };
+        let mut r#ref = Referee::new(0);
 
-    let mut r#ref = Referee::new(0);
+        let game_result = r#ref.run_from_state(&mut state, &mut observers, goals.into());
+        let mut winner_names: Vec<Name> =
+            game_result.winners.into_iter().map(|w| w.name()).collect();
+        winner_names.sort();
 
-    let game_result = r#ref.run_from_state(&mut state, &mut observers, goals.into());
-    let mut winner_names: Vec<Name> = game_result.winners.into_iter().map(|w| w.name()).collect();
-    winner_names.sort();
+        if games_played > 0 {
+            writer.write_all(b"\n")?;
+        }
+        write_json_out_to_writer(winner_names, writer)?;
+        games_played += 1;
+    }
 
-    write_json_out_to_writer(winner_names, writer)?;
+    if games_played == 0 {
+        Err(anyhow!("Did not recieve a PlayerSpec array"))?;
+    }
 
     Ok(())
 }
@@ -59,4 +59,29 @@ mod tests {
             assert_eq!(input, output);
         }
     }
+
+    #[test]
+    fn test_multiple_games() {
+        let read =
+            |name: &str| std::fs::read_to_string(Path::new("./../Tests/").join(name)).unwrap();
+        let parse = |str: &str| {
+            common::json::json_stream(str.as_bytes())
+                .collect::<Result<Vec<serde_json::Value>, _>>()
+                .unwrap()
+        };
+
+        let input = format!("{}\n{}", read("0-in.json"), read("1-in.json"));
+        let mut buf = Vec::new();
+        read_and_write_json(input.as_bytes(), &mut buf, vec![]).unwrap();
+        let expected = format!("{}\n{}", read("0-out.json"), read("1-out.json"));
+        assert_eq!(parse(&String::from_utf8(buf).unwrap()), parse(&expected));
+
+        let mut buf = Vec::new();
+        read_and_write_json(read("1-in.json").as_bytes(), &mut buf, vec![]).unwrap();
+        let output = parse(&String::from_utf8(buf).unwrap());
+        assert_eq!(output.len(), 1);
+        assert_eq!(output, parse(&read("1-out.json")));
+
+        assert!(read_and_write_json("".as_bytes(), &mut Vec::new(), vec![]).is_err());
+    }
 }
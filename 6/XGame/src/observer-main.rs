@@ -1,22 +1,40 @@
 use std::{
-    io::{stdin, stdout},
+    fs::File,
+    io::{stdin, stdout, BufReader},
+    path::PathBuf,
     thread,
 };
 
+use clap::Parser;
 use egui::Vec2;
 use referee::observer::ObserverGUI;
 use xgames::*;
 
-fn main() {
+#[derive(Parser)]
+struct Args {
+    /// Steps through a transcript of saved states instead of running a game from STDIN
+    #[arg(long)]
+    replay: Option<PathBuf>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let Args { replay } = Args::parse();
+
     let height = 700.0;
     let width = 800.0;
-    let observer = ObserverGUI::default();
     let options = eframe::NativeOptions {
         initial_window_size: Option::from(Vec2::new(width, height)),
         resizable: false,
         ..Default::default()
     };
 
+    if let Some(path) = replay {
+        let observer = ObserverGUI::from_transcript(BufReader::new(File::open(path)?))?;
+        eframe::run_native("Observer", options, Box::new(move |_cc| Box::new(observer)));
+        return Ok(());
+    }
+
+    let observer = ObserverGUI::default();
     let observer_clone = observer.clone();
     let guard = thread::spawn(|| {
         read_and_write_json(
@@ -29,4 +47,5 @@ fn main() {
 
     eframe::run_native("Observer", options, Box::new(move |_cc| Box::new(observer)));
     guard.join().unwrap();
+    Ok(())
 }
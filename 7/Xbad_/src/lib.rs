@@ -3,7 +3,7 @@ use std::io::{Read, Write};
 use anyhow::{anyhow, bail};
 use common::{
     grid::Position,
-    json::Name,
+    json::{json_stream, Name},
     state::{FullPlayerInfo, State},
 };
 use players::{
@@ -27,17 +27,16 @@ enum ValidJson {
 }
 
 /// Turn the `impl Read` into A `ValidJson` Stream
-fn get_json_iter_from_reader(reader: impl Read) -> anyhow::Result<impl Iterator<Item = ValidJson>> {
-    let deserializer = serde_json::Deserializer::from_reader(reader);
-    Ok(deserializer
-        .into_iter::<ValidJson>()
-        .collect::<Result<Vec<_>, _>>()?
-        .into_iter())
+fn get_json_iter_from_reader(
+    reader: impl Read,
+) -> impl Iterator<Item = serde_json::Result<ValidJson>> {
+    json_stream(reader)
 }
 
-/// Writes the `impl Serialize` to the `impl Write`
+/// Writes all of the `impl Serialize` to the `impl Write` and flushes it
 fn write_json_out_to_writer(output: impl Serialize, writer: &mut impl Write) -> anyhow::Result<()> {
     writer.write_all(serde_json::to_string(&output)?.as_bytes())?;
+    writer.flush()?;
     Ok(())
 }
 
@@ -46,11 +45,11 @@ pub fn read_and_write_json(
     writer: &mut impl Write,
     mut observers: Vec<Box<dyn Observer>>,
 ) -> anyhow::Result<()> {
-    let mut input = get_json_iter_from_reader(reader)?;
+    let mut input = get_json_iter_from_reader(reader);
 
     let players: Vec<Box<dyn PlayerApi + Send>> = match input
         .next()
-        .ok_or_else(|| anyhow!("Did not recieve JSON"))?
+        .ok_or_else(|| anyhow!("Did not recieve JSON"))??
     {
         ValidJson::PlayerSpec(pss) => pss
             .into_iter()
@@ -76,7 +75,7 @@ pub fn read_and_write_json(
 
     let (state, goals): (State<FullPlayerInfo>, Vec<Position>) = match input
         .next()
-        .ok_or_else(|| anyhow!("Did not receive JSON"))?
+        .ok_or_else(|| anyhow!("Did not receive JSON"))??
     {
         ValidJson::RefereeState(a) => a.try_into()?,
         _ => bail!(""),
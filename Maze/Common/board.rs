@@ -1,24 +1,56 @@
 use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
 use thiserror::Error;
+use unordered_pair::UnorderedPair;
 
-use crate::gem::Gem;
+use crate::gem::{Gem, GemSource, SequentialGems};
 use crate::grid::{Grid, Position};
-use crate::tile::{CompassDirection, ConnectorShape, Tile};
-use std::collections::HashSet;
+use crate::json::has_unique_elements;
+use crate::tile::{Axis, CompassDirection, ConnectorShape, Tile};
+use std::collections::{hash_map::Entry, HashMap, HashSet, VecDeque};
+use std::fmt;
 use std::ops::Index;
 
 #[derive(Debug, Error)]
 pub enum OutOfBounds {
     #[error("{0} is out of bounds!")]
     Index(usize),
-    #[error("{0:?} is out of bounds!")]
-    Position(Position),
+    /// A `Position` outside of a board with the given number of columns and rows
+    #[error("{0:?} is out of bounds! The valid range is 0..{1} x 0..{2}")]
+    Position(Position, usize, usize),
 }
 
 pub type BoardResult<T> = Result<T, OutOfBounds>;
 
+/// Describes why a connector string could not be parsed into a `Board`
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ConnectorsError {
+    #[error("{0:?} at {1:?} is not a connector!")]
+    InvalidChar(char, Position),
+    #[error("row {0} has {1} connectors, but the first row has {2}!")]
+    RaggedRow(usize, usize, usize),
+    #[error("a board needs at least one connector!")]
+    Empty,
+}
+
+/// Describes why a `Slide` could not be made on a `Board`
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SlideError {
+    /// The index is past the last row or column, which is the given length
+    #[error("{0} is out of range! There are only {1} lines to slide along that axis")]
+    IndexOutOfRange(usize, usize),
+    /// The row or column exists, but it cannot be slid
+    #[error("line {0} cannot be slid!")]
+    ImmovableLine(usize),
+}
+
+/// A `GemSource` handed out the same pair of gems for more than one tile
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("the gems {0:?} were handed out for more than one tile!")]
+pub struct DuplicateGems(pub UnorderedPair<Gem>);
+
 /// Describes one board for the game of Maze`.`com
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Board {
     pub grid: Grid<Tile>,
     pub spare: Tile,
@@ -50,11 +82,24 @@ impl Board {
         (0..self.num_cols()).step_by(2)
     }
 
+    /// Returns every slideable row tagged with `Axis::Horizontal`, followed by every slideable
+    /// column tagged with `Axis::Vertical`.
+    pub fn slideable_lines(&self) -> impl Iterator<Item = (Axis, usize)> {
+        self.slideable_rows()
+            .map(|row| (Axis::Horizontal, row))
+            .chain(self.slideable_cols().map(|col| (Axis::Vertical, col)))
+    }
+
     #[must_use]
     pub fn in_bounds(&self, pos: &Position) -> bool {
         (0..self.num_cols()).contains(&pos.0) && (0..self.num_rows()).contains(&pos.1)
     }
 
+    /// Returns the error describing `pos` being outside of this `Board`
+    pub fn out_of_bounds(&self, pos: Position) -> OutOfBounds {
+        OutOfBounds::Position(pos, self.num_cols(), self.num_rows())
+    }
+
     pub fn possible_homes(&self) -> impl Iterator<Item = Position> {
         let slideable_cols = self.slideable_cols().collect::<Vec<_>>();
         let slideable_rows = self.slideable_cols().collect::<Vec<_>>();
@@ -71,47 +116,42 @@ impl Board {
 
     /// Slides the given Slide struct command and inserts the spare tile in the location of the
     /// hole in the board. The dislodged tile becomes the new `spare_tile`.
-    pub fn slide_and_insert(&mut self, Slide { index, direction }: Slide) -> BoardResult<()> {
+    ///
+    /// On a 1x1 `Board`, every slide swaps the only tile with the spare tile.
+    pub fn slide_and_insert(&mut self, slide: Slide) -> BoardResult<()> {
         use CompassDirection::*;
+        let Slide { index, direction } = slide;
         match direction {
-            North => {
-                if index > self.num_cols() {
-                    return Err(OutOfBounds::Index(index));
-                }
-                let col_num = index;
-                let row_num = self.grid.len() - 1;
-                self.grid.rotate_up(col_num);
-                std::mem::swap(&mut self.spare, &mut self.grid[(col_num, row_num)]);
-                Ok(())
-            }
-            South => {
-                if index > self.num_cols() {
-                    return Err(OutOfBounds::Index(index));
-                }
-                let col_num = index;
-                self.grid.rotate_down(col_num);
-                std::mem::swap(&mut self.spare, &mut self.grid[(col_num, 0)]);
-                Ok(())
-            }
-            East => {
-                if index > self.num_rows() {
-                    return Err(OutOfBounds::Index(index));
-                }
-                let row_num = index;
-                self.grid.rotate_right(row_num);
-                std::mem::swap(&mut self.spare, &mut self.grid[(0, row_num)]);
-                Ok(())
-            }
-            West => {
-                if index > self.num_rows() {
-                    return Err(OutOfBounds::Index(index));
-                }
-                let row_num = index;
-                let col_num = self.grid[0].len() - 1;
-                self.grid.rotate_left(row_num);
-                std::mem::swap(&mut self.spare, &mut self.grid[(col_num, row_num)]);
-                Ok(())
-            }
+            North | South if index > self.num_cols() => return Err(OutOfBounds::Index(index)),
+            East | West if index > self.num_rows() => return Err(OutOfBounds::Index(index)),
+            North => self.grid.rotate_up(index),
+            South => self.grid.rotate_down(index),
+            East => self.grid.rotate_right(index),
+            West => self.grid.rotate_left(index),
+        }
+        let insert_at = self.insertion_position(slide);
+        std::mem::swap(&mut self.spare, &mut self.grid[insert_at]);
+        Ok(())
+    }
+
+    /// Returns the `Position` the spare tile is inserted at when `slide` is made: the end of the
+    /// row or column that the slide moves away from.
+    ///
+    /// ```
+    /// use common::board::{Board, Slide};
+    /// use common::tile::CompassDirection;
+    ///
+    /// let board = Board::default();
+    /// let slide = Slide::new_unchecked(0, CompassDirection::North);
+    /// assert_eq!(board.insertion_position(slide), (0, 6));
+    /// ```
+    pub fn insertion_position(&self, Slide { index, direction }: Slide) -> Position {
+        use CompassDirection::*;
+        match direction {
+            North => (index, self.num_rows() - 1),
+            South => (index, 0),
+            East => (0, index),
+            West => (self.num_cols() - 1, index),
         }
     }
 
@@ -154,7 +194,7 @@ impl Board {
     /// `start.1` are negative.
     pub fn reachable(&self, start: Position) -> BoardResult<Vec<Position>> {
         if start.0 >= self.grid[0].len() || start.1 >= self.grid.len() {
-            return Err(OutOfBounds::Position(start));
+            return Err(self.out_of_bounds(start));
         }
 
         // push start onto worklist
@@ -177,9 +217,221 @@ impl Board {
         Ok(reachable.into_iter().collect())
     }
 
+    /// Returns the `Position` reachable from `start` that takes the most steps to get to, along
+    /// with that number of steps. Ties are broken by picking the first `Position` in row-column
+    /// order.
+    ///
+    /// # Errors
+    /// Returns an error if `start` is out of bounds.
+    pub fn farthest_reachable(&self, start: Position) -> BoardResult<(Position, usize)> {
+        if !self.in_bounds(&start) {
+            return Err(self.out_of_bounds(start));
+        }
+
+        let mut depths = HashMap::from([(start, 0)]);
+        let mut worklist = VecDeque::from([start]);
+        while let Some(curr) = worklist.pop_front() {
+            let depth = depths[&curr];
+            for neighbor in self.reachable_neighbors(curr) {
+                if let Entry::Vacant(entry) = depths.entry(neighbor) {
+                    entry.insert(depth + 1);
+                    worklist.push_back(neighbor);
+                }
+            }
+        }
+
+        Ok(depths
+            .into_iter()
+            .max_by(|(p1, d1), (p2, d2)| d1.cmp(d2).then((p2.1, p2.0).cmp(&(p1.1, p1.0))))
+            .expect("`start` is always reachable"))
+    }
+
+    /// Returns every `Position` that can be reached from `start` in at most `k` steps, including
+    /// `start` itself.
+    ///
+    /// # Errors
+    /// Returns an error if `start` is out of bounds.
+    pub fn reachable_within(&self, start: Position, k: usize) -> BoardResult<HashSet<Position>> {
+        if !self.in_bounds(&start) {
+            return Err(self.out_of_bounds(start));
+        }
+
+        let mut reached = HashSet::from([start]);
+        let mut frontier = vec![start];
+        for _ in 0..k {
+            frontier = frontier
+                .into_iter()
+                .flat_map(|curr| self.reachable_neighbors(curr))
+                .filter(|&neighbor| reached.insert(neighbor))
+                .collect();
+            if frontier.is_empty() {
+                break;
+            }
+        }
+
+        Ok(reached)
+    }
+
+    /// Checks that no two tiles on this `Board`, including the spare, have the same pair of gems.
+    #[must_use]
+    pub fn has_unique_gems(&self) -> bool {
+        has_unique_elements(
+            self.grid
+                .iter()
+                .flat_map(|row| row.iter())
+                .chain(std::iter::once(&self.spare))
+                .map(|tile| &tile.gems),
+        )
+    }
+
+    /// Returns a `Grid` with the same shape as this `Board`, where every cell holds the number of
+    /// directions the connector of that tile is open towards, from 0 to 4.
+    pub fn connectivity_map(&self) -> Grid<u8> {
+        self.grid
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|tile| tile.connector.open_directions().len() as u8)
+                    .collect::<Box<[_]>>()
+            })
+            .collect::<Box<[_]>>()
+            .into()
+    }
+
+    /// Returns the number of groups of tiles on this `Board` that are connected to each other but
+    /// not to any tile outside of their group. A `Board` where every tile can reach every other
+    /// tile has exactly one component.
+    pub fn num_components(&self) -> usize {
+        let mut seen = HashSet::new();
+        let mut components = 0;
+        for pos in self.grid.iter_positions() {
+            if !seen.contains(&pos) {
+                components += 1;
+                seen.extend(self.reachable(pos).expect("pos is in bounds"));
+            }
+        }
+        components
+    }
+
     pub fn rotate_spare(&mut self) {
         self.spare.rotate();
     }
+
+    /// Returns the connector the spare `Tile` would have after `n` rotations, without rotating it
+    #[must_use]
+    pub fn spare_after_rotations(&self, n: usize) -> ConnectorShape {
+        (0..n % 4).fold(self.spare.connector, |connector, _| connector.rotate())
+    }
+
+    /// Returns the positions of every `Tile` on the grid that has `gem` as one of its gems, in
+    /// row-column order. The spare tile is not on the grid, so it is never included.
+    pub fn positions_with_gem(&self, gem: Gem) -> Vec<Position> {
+        self.grid
+            .iter()
+            .enumerate()
+            .flat_map(|(row_idx, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, tile)| tile.gems.0 == gem || tile.gems.1 == gem)
+                    .map(move |(col_idx, _)| (col_idx, row_idx))
+            })
+            .collect()
+    }
+
+    /// Checks if `self` and `other` have the same `ConnectorShape`s in every cell and the same
+    /// spare `ConnectorShape`, ignoring the gems on every `Tile`.
+    #[must_use]
+    pub fn same_connectors(&self, other: &Board) -> bool {
+        self.spare.connector == other.spare.connector
+            && self.num_rows() == other.num_rows()
+            && self.num_cols() == other.num_cols()
+            && self
+                .grid
+                .iter()
+                .flat_map(|row| row.iter())
+                .zip(other.grid.iter().flat_map(|row| row.iter()))
+                .all(|(this, other)| this.connector == other.connector)
+    }
+}
+
+// For connector strings
+impl Board {
+    /// Returns the `ConnectorShape`s of this `Board` as box-drawing characters, one line per row.
+    /// Gems and the spare tile are left out.
+    ///
+    /// ```
+    /// use common::board::{Board, DefaultBoard};
+    ///
+    /// let board: Board = DefaultBoard::<3, 3>::default_board();
+    /// assert_eq!(board.connectors_string(), "─│└\n┌┐┘\n┴├┬");
+    /// ```
+    pub fn connectors_string(&self) -> String {
+        self.grid
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|tile| tile.connector.to_char())
+                    .collect::<String>()
+            })
+            .join("\n")
+    }
+
+    /// Parses a `Board` from box-drawing characters laid out like `Board::connectors_string`.
+    /// Surrounding whitespace and blank lines are ignored, so diagrams can be indented.
+    ///
+    /// `gems` hands out a pair for every tile in row-major order, and then one more for the spare
+    /// tile, which is always a `ConnectorShape::Crossroads`.
+    ///
+    /// # Errors
+    /// Returns an error if a character is not a connector, if the rows have different lengths, or
+    /// if there are no connectors at all.
+    pub fn from_connectors_str(
+        s: &str,
+        mut gems: impl GemSource,
+    ) -> Result<Board, ConnectorsError> {
+        let lines = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        let num_cols = lines
+            .first()
+            .map(|line| line.chars().count())
+            .ok_or(ConnectorsError::Empty)?;
+
+        let mut rows = Vec::with_capacity(lines.len());
+        for (row_idx, line) in lines.into_iter().enumerate() {
+            let row = line
+                .chars()
+                .enumerate()
+                .map(|(col_idx, c)| {
+                    ConnectorShape::from_char(c)
+                        .ok_or(ConnectorsError::InvalidChar(c, (col_idx, row_idx)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if row.len() != num_cols {
+                return Err(ConnectorsError::RaggedRow(row_idx, row.len(), num_cols));
+            }
+            rows.push(row);
+        }
+
+        let grid = rows
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|connector| Tile {
+                        connector,
+                        gems: gems.next_pair(),
+                    })
+                    .collect::<Box<[_]>>()
+            })
+            .collect::<Box<[_]>>();
+        let spare = Tile {
+            connector: ConnectorShape::Crossroads,
+            gems: gems.next_pair(),
+        };
+        Ok(Board::new(grid, spare))
+    }
 }
 
 impl Index<Position> for Board {
@@ -236,6 +488,104 @@ impl Default for Board {
     }
 }
 
+impl Board {
+    /// Generates a random `COLS` x `ROWS` `Board` where every tile can reach every other tile,
+    /// so the returned `Board` always has exactly one component.
+    ///
+    /// Gems are handed out by `SequentialGems`, in the same order as
+    /// `DefaultBoard::default_board`.
+    ///
+    /// # Errors
+    /// Returns an error if the `Board` has too many tiles for `SequentialGems` to give each of
+    /// them a unique pair of gems.
+    pub fn random_connected<const COLS: usize, const ROWS: usize>(
+        rng: &mut impl Rng,
+    ) -> Result<Board, DuplicateGems> {
+        Self::random_connected_with_gems::<COLS, ROWS>(rng, SequentialGems::default())
+    }
+
+    /// Generates a random `COLS` x `ROWS` `Board` like `Board::random_connected`, taking the gems
+    /// of every tile in row-major order, and then of the spare tile, from `gems`.
+    ///
+    /// A random spanning tree of the grid is built first, then every tile is given a random
+    /// `ConnectorShape` that opens towards each of its neighbors in that tree.
+    ///
+    /// # Errors
+    /// Returns an error if `gems` hands out the same pair of gems twice.
+    pub fn random_connected_with_gems<const COLS: usize, const ROWS: usize>(
+        rng: &mut impl Rng,
+        mut gems: impl GemSource,
+    ) -> Result<Board, DuplicateGems> {
+        use CompassDirection::*;
+        let mut openings: HashMap<Position, Vec<CompassDirection>> = HashMap::new();
+        let mut visited: HashSet<Position> = HashSet::from([(0, 0)]);
+        let mut worklist: Vec<Position> = vec![(0, 0)];
+        while let Some(&(col, row)) = worklist.last() {
+            let unvisited = [
+                (North, row.checked_sub(1).map(|row| (col, row))),
+                (East, (col + 1 < COLS).then_some((col + 1, row))),
+                (South, (row + 1 < ROWS).then_some((col, row + 1))),
+                (West, col.checked_sub(1).map(|col| (col, row))),
+            ]
+            .into_iter()
+            .filter_map(|(dir, next)| Some((dir, next?)))
+            .filter(|(_, next)| !visited.contains(next))
+            .collect_vec();
+
+            match unvisited.choose(rng) {
+                Some(&(dir, next)) => {
+                    openings.entry((col, row)).or_default().push(dir);
+                    openings.entry(next).or_default().push(dir.opposite());
+                    visited.insert(next);
+                    worklist.push(next);
+                }
+                None => {
+                    worklist.pop();
+                }
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut next_unique = || {
+            let pair = gems.next_pair();
+            if seen.insert(pair) {
+                Ok(pair)
+            } else {
+                Err(DuplicateGems(pair))
+            }
+        };
+
+        let shapes = (0..11).map(ConnectorShape::from_num).collect_vec();
+        let grid = (0..ROWS)
+            .map(|row| {
+                (0..COLS)
+                    .map(|col| {
+                        let needed = openings.remove(&(col, row)).unwrap_or_default();
+                        let candidates = shapes
+                            .iter()
+                            .filter(|shape| needed.iter().all(|&dir| shape.connected_to(dir)))
+                            .collect_vec();
+                        Ok::<_, DuplicateGems>(Tile {
+                            connector: **candidates
+                                .choose(rng)
+                                .expect("Crossroads connects in every direction"),
+                            gems: next_unique()?,
+                        })
+                    })
+                    .collect::<Result<Box<[_]>, _>>()
+            })
+            .collect::<Result<Box<[_]>, _>>()?;
+
+        Ok(Self {
+            grid: Grid::from(grid),
+            spare: Tile {
+                connector: *shapes.choose(rng).expect("there are 11 shapes"),
+                gems: next_unique()?,
+            },
+        })
+    }
+}
+
 /// Describes a slide motion
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Slide {
@@ -261,28 +611,108 @@ impl Board {
     /// assert!(board.new_slide(4, CompassDirection::East).is_some());
     /// ```
     pub fn new_slide(&self, index: usize, direction: CompassDirection) -> Option<Slide> {
+        self.make_slide(index, direction).ok()
+    }
+
+    /// Creates a slide command, describing why it is not valid on this `Board` if it is not.
+    ///
+    /// # Errors
+    /// Returns `SlideError::IndexOutOfRange` if `index` is past the last row (or column, for
+    /// vertical slides), and `SlideError::ImmovableLine` if that row or column cannot be slid.
+    pub fn make_slide(
+        &self,
+        index: usize,
+        direction: CompassDirection,
+    ) -> Result<Slide, SlideError> {
+        let len = match direction.axis() {
+            Axis::Horizontal => self.num_rows(),
+            Axis::Vertical => self.num_cols(),
+        };
         let slide = Slide::new_unchecked(index, direction);
-        self.valid_slide(slide).then_some(slide)
+        if index >= len {
+            Err(SlideError::IndexOutOfRange(index, len))
+        } else if !self.valid_slide(slide) {
+            Err(SlideError::ImmovableLine(index))
+        } else {
+            Ok(slide)
+        }
     }
 
     pub fn valid_slide(&self, Slide { index, direction }: Slide) -> bool {
-        match direction {
-            CompassDirection::North | CompassDirection::South
-                if self.slideable_cols().contains(&index) =>
-            {
-                true
-            }
-            CompassDirection::East | CompassDirection::West
-                if self.slideable_rows().contains(&index) =>
-            {
-                true
+        match direction.axis() {
+            Axis::Horizontal => self.slideable_rows().contains(&index),
+            Axis::Vertical => self.slideable_cols().contains(&index),
+        }
+    }
+
+    /// Returns every valid `Slide` on this `Board`: each slideable row sliding `West` then `East`,
+    /// followed by each slideable column sliding `North` then `South`.
+    pub fn all_slides(&self) -> Vec<Slide> {
+        use CompassDirection::*;
+        self.slideable_lines()
+            .flat_map(|(axis, index)| {
+                let directions = match axis {
+                    Axis::Horizontal => [West, East],
+                    Axis::Vertical => [North, South],
+                };
+                directions.map(|direction| Slide::new_unchecked(index, direction))
+            })
+            .collect()
+    }
+
+    /// Slides and inserts every `Slide` in `slides`, in order.
+    ///
+    /// # Errors
+    /// Returns the `SlideError` from `Board::make_slide` for the first of the `slides` that is not
+    /// valid on this `Board`. In that case, none of the `slides` are applied and the `Board` is
+    /// left unchanged.
+    pub fn apply_slides(&mut self, slides: &[Slide]) -> Result<(), SlideError> {
+        let original = self.clone();
+        for &Slide { index, direction } in slides {
+            match self.make_slide(index, direction) {
+                Ok(slide) => self
+                    .slide_and_insert(slide)
+                    .expect("make_slide only returns valid slides"),
+                Err(err) => {
+                    *self = original;
+                    return Err(err);
+                }
             }
-            _ => false,
         }
+        Ok(())
+    }
+}
+
+/// Displays as `row 2 East` or `column 0 North`
+impl fmt::Display for Slide {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line = if self.direction.is_horizontal() {
+            "row"
+        } else {
+            "column"
+        };
+        write!(f, "{} {} {:?}", line, self.index, self.direction)
     }
 }
 
 impl Slide {
+    /// Creates a `Slide` if it is valid on `board`. This is the same as `Board::new_slide`.
+    ///
+    /// ```
+    /// use common::board::{Board, DefaultBoard, Slide};
+    /// use common::tile::CompassDirection;
+    ///
+    /// let board: Board = DefaultBoard::<7,7>::default_board();
+    /// assert!(Slide::try_new(&board, 3, CompassDirection::North).is_none());
+    /// assert!(Slide::try_new(&board, 4, CompassDirection::East).is_some());
+    /// ```
+    pub fn try_new(board: &Board, index: usize, direction: CompassDirection) -> Option<Slide> {
+        board.new_slide(index, direction)
+    }
+
+    /// Creates a `Slide` without checking that it is valid on any `Board`.
+    ///
+    /// Use `Slide::try_new` or `Board::new_slide` to get a `Slide` that is known to be valid.
     pub fn new_unchecked(index: usize, direction: CompassDirection) -> Slide {
         Self { index, direction }
     }
@@ -339,6 +769,9 @@ impl Slide {
 #[cfg(test)]
 mod board_tests {
     use super::*;
+    use crate::gem::RandomGems;
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
     use CompassDirection::*;
     use ConnectorShape::*;
 
@@ -354,6 +787,23 @@ mod board_tests {
         assert!(seven_by_seven.new_slide(5, West).is_none());
     }
 
+    #[test]
+    fn test_slide_try_new() {
+        let b: Board = DefaultBoard::<7, 7>::default_board();
+        assert_eq!(Slide::try_new(&b, 3, North), None);
+        assert_eq!(
+            Slide::try_new(&b, 4, East),
+            Some(Slide::new_unchecked(4, East))
+        );
+        assert_eq!(Slide::try_new(&b, 8, South), None);
+    }
+
+    #[test]
+    fn test_slide_display() {
+        assert_eq!(Slide::new_unchecked(2, East).to_string(), "row 2 East");
+        assert_eq!(Slide::new_unchecked(0, North).to_string(), "column 0 North");
+    }
+
     #[test]
     fn test_slide_move_position() {
         let b: Board = DefaultBoard::<7, 7>::default_board();
@@ -416,6 +866,53 @@ mod board_tests {
         assert_eq!(b.spare.connector, Corner(East));
     }
 
+    #[test]
+    pub fn test_one_by_one_board() {
+        let mut board: Board = DefaultBoard::<1, 1>::default_board();
+        let tile = board[(0, 0)].clone();
+        let spare = board.spare.clone();
+        assert_eq!(board.reachable((0, 0)).unwrap(), vec![(0, 0)]);
+        assert_eq!(
+            board.reachable_within((0, 0), 5).unwrap(),
+            HashSet::from([(0, 0)])
+        );
+
+        let slide = board.new_slide(0, North).unwrap();
+        assert!(board.slide_and_insert(slide).is_ok());
+        assert_eq!(board[(0, 0)], spare);
+        assert_eq!(board.spare, tile);
+        assert_eq!(slide.move_position((0, 0), 1, 1), (0, 0));
+        assert_eq!(board.reachable((0, 0)).unwrap(), vec![(0, 0)]);
+
+        for direction in [South, East, West] {
+            let slide = board.new_slide(0, direction).unwrap();
+            let before = board.clone();
+            assert!(board.slide_and_insert(slide).is_ok());
+            assert_eq!(board[(0, 0)], before.spare);
+            assert_eq!(board.spare, before[(0, 0)]);
+            assert_eq!(slide.move_position((0, 0), 1, 1), (0, 0));
+        }
+        assert_eq!(board.farthest_reachable((0, 0)).unwrap(), ((0, 0), 0));
+        assert_eq!(board.num_components(), 1);
+    }
+
+    #[test]
+    pub fn test_insertion_position() {
+        let board = Board::default();
+        let insertion =
+            |index, direction| board.insertion_position(board.new_slide(index, direction).unwrap());
+        assert_eq!(insertion(0, North), (0, 6));
+        assert_eq!(insertion(0, South), (0, 0));
+        assert_eq!(insertion(2, East), (0, 2));
+        assert_eq!(insertion(2, West), (6, 2));
+
+        for slide in board.all_slides() {
+            let mut slid = board.clone();
+            slid.slide_and_insert(slide).unwrap();
+            assert_eq!(slid[board.insertion_position(slide)], board.spare);
+        }
+    }
+
     #[test]
     pub fn test_reachable_from_position() {
         // Default Board<3> is:
@@ -440,6 +937,7 @@ mod board_tests {
         // extra = ┼
         let b: Board = DefaultBoard::<3, 3>::default_board();
         assert!(b.reachable((10, 10)).is_err());
+        assert!(b.reachable((3, 0)).is_err());
         let from_0_0 = b.reachable((0, 0));
         assert!(from_0_0.is_ok());
         assert_eq!(from_0_0.unwrap().len(), 1);
@@ -447,4 +945,440 @@ mod board_tests {
         assert!(from_2_2.is_ok());
         assert_eq!(from_2_2.unwrap().len(), 5);
     }
+
+    #[test]
+    pub fn test_out_of_bounds_message() {
+        let b: Board = DefaultBoard::<7, 7>::default_board();
+        let err = b.reachable((10, 10)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "(10, 10) is out of bounds! The valid range is 0..7 x 0..7"
+        );
+
+        let b: Board = DefaultBoard::<5, 3>::default_board();
+        assert!(b
+            .farthest_reachable((4, 3))
+            .unwrap_err()
+            .to_string()
+            .contains("valid range is 0..5 x 0..3"));
+    }
+
+    #[test]
+    pub fn test_board_hash() {
+        let board = Board::default();
+        let mut swapped = board.clone();
+        let UnorderedPair(first, second) = swapped.grid[(1, 0)].gems;
+        swapped.grid[(1, 0)].gems = UnorderedPair(second, first);
+
+        let mut boards = HashSet::new();
+        boards.insert(board.clone());
+        boards.insert(DefaultBoard::<7, 7>::default_board());
+        boards.insert(swapped);
+        assert_eq!(boards.len(), 1);
+
+        let mut slid = board.clone();
+        slid.slide_and_insert(Slide::new_unchecked(0, East))
+            .unwrap();
+        boards.insert(slid.clone());
+        assert_eq!(boards.len(), 2);
+
+        slid.rotate_spare();
+        boards.insert(slid);
+        assert_eq!(boards.len(), 3);
+    }
+
+    #[test]
+    pub fn test_same_connectors() {
+        let mut b: Board = DefaultBoard::<3, 3>::default_board();
+        // the default spare is a crossroads, which looks the same after rotating it
+        b.spare.connector = Corner(North);
+        let mut swapped = b.clone();
+        swapped.grid[(0, 0)].gems = b.grid[(1, 1)].gems;
+        swapped.grid[(1, 1)].gems = b.grid[(0, 0)].gems;
+        assert!(b.same_connectors(&swapped));
+        assert_ne!(b, swapped);
+
+        let mut rotated = b.clone();
+        rotated.rotate_spare();
+        assert!(!b.same_connectors(&rotated));
+
+        let mut slid = b.clone();
+        slid.slide_and_insert(b.new_slide(0, South).unwrap())
+            .unwrap();
+        assert!(!b.same_connectors(&slid));
+
+        let bigger: Board = DefaultBoard::<5, 5>::default_board();
+        assert!(!b.same_connectors(&bigger));
+    }
+
+    #[test]
+    pub fn test_make_slide() {
+        let board = Board::default();
+        assert_eq!(board.make_slide(5, East), Err(SlideError::ImmovableLine(5)));
+        assert_eq!(
+            board.make_slide(20, East),
+            Err(SlideError::IndexOutOfRange(20, 7))
+        );
+        assert_eq!(board.make_slide(2, East), Ok(Slide::new_unchecked(2, East)));
+
+        let board = Board::random_connected::<5, 3>(&mut ChaChaRng::seed_from_u64(0)).unwrap();
+        assert_eq!(
+            board.make_slide(4, North),
+            Ok(Slide::new_unchecked(4, North))
+        );
+        assert_eq!(
+            board.make_slide(4, West),
+            Err(SlideError::IndexOutOfRange(4, 3))
+        );
+    }
+
+    #[test]
+    pub fn test_all_slides() {
+        let b: Board = DefaultBoard::<7, 7>::default_board();
+        let slides = b.all_slides();
+        assert_eq!(slides.len(), 16);
+        assert!(slides.iter().all(|&slide| b.valid_slide(slide)));
+        assert!(slides
+            .iter()
+            .enumerate()
+            .all(|(idx, slide)| !slides[idx + 1..].contains(slide)));
+        assert_eq!(slides[0], Slide::new_unchecked(0, West));
+        assert_eq!(slides[15], Slide::new_unchecked(6, South));
+
+        let one_by_one: Board = DefaultBoard::<1, 1>::default_board();
+        assert_eq!(one_by_one.all_slides().len(), 4);
+    }
+
+    #[test]
+    pub fn test_slideable_lines() {
+        let b: Board = DefaultBoard::<7, 7>::default_board();
+        assert_eq!(
+            b.slideable_lines().collect::<Vec<_>>(),
+            vec![
+                (Axis::Horizontal, 0),
+                (Axis::Horizontal, 2),
+                (Axis::Horizontal, 4),
+                (Axis::Horizontal, 6),
+                (Axis::Vertical, 0),
+                (Axis::Vertical, 2),
+                (Axis::Vertical, 4),
+                (Axis::Vertical, 6),
+            ]
+        );
+
+        let wide: Board = DefaultBoard::<5, 2>::default_board();
+        assert_eq!(
+            wide.slideable_lines().collect::<Vec<_>>(),
+            vec![
+                (Axis::Horizontal, 0),
+                (Axis::Vertical, 0),
+                (Axis::Vertical, 2),
+                (Axis::Vertical, 4),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_reachable_within() {
+        let board: Board = DefaultBoard::<3, 3>::default_board();
+        // ─│└
+        // ┌┐┘
+        // ┴├┬
+        assert_eq!(
+            board.reachable_within((0, 1), 0).unwrap(),
+            HashSet::from([(0, 1)])
+        );
+        assert_eq!(
+            board.reachable_within((0, 1), 1).unwrap(),
+            HashSet::from([(0, 1), (1, 1), (0, 2)])
+        );
+        assert_eq!(
+            board.reachable_within((0, 1), 2).unwrap(),
+            HashSet::from([(0, 1), (1, 1), (0, 2), (1, 2)])
+        );
+        assert_eq!(
+            board.reachable_within((0, 1), 100).unwrap(),
+            board
+                .reachable((0, 1))
+                .unwrap()
+                .into_iter()
+                .collect::<HashSet<_>>()
+        );
+        assert_eq!(
+            board.reachable_within((0, 0), 100).unwrap(),
+            HashSet::from([(0, 0)])
+        );
+        assert!(board.reachable_within((3, 0), 1).is_err());
+    }
+
+    #[test]
+    pub fn test_farthest_reachable() {
+        // Default Board<3> is:
+        // ─│└
+        // ┌┐┘
+        // ┴├┬
+        // extra = ┼
+        let b: Board = DefaultBoard::<3, 3>::default_board();
+        assert!(b.farthest_reachable((3, 0)).is_err());
+        assert_eq!(b.farthest_reachable((0, 0)).unwrap(), ((0, 0), 0));
+        assert_eq!(b.farthest_reachable((2, 2)).unwrap(), ((0, 2), 4));
+        assert_eq!(b.farthest_reachable((1, 1)).unwrap(), ((0, 2), 2));
+    }
+
+    #[test]
+    pub fn test_connectors_string() {
+        let b: Board = DefaultBoard::<7, 7>::default_board();
+        let diagram = b.connectors_string();
+        assert_eq!(
+            diagram,
+            "─│└┌┐┘┴\n├┬┤┼─│└\n┌┐┘┴├┬┤\n┼─│└┌┐┘\n┴├┬┤┼─│\n└┌┐┘┴├┬\n┤┼─│└┌┐"
+        );
+
+        let mut idx = 0;
+        let parsed = Board::from_connectors_str(&diagram, || {
+            idx += 1;
+            Gem::pair_from_num(idx - 1)
+        })
+        .unwrap();
+        assert_eq!(parsed.grid, b.grid);
+        assert!(parsed.same_connectors(&b));
+
+        // indented diagrams, like the ones in test comments, parse too
+        let parsed = Board::from_connectors_str(
+            "
+            ─│└
+            ┌┐┘
+            ┴├┬
+            ",
+            || Gem::pair_from_num(0),
+        )
+        .unwrap();
+        assert!(parsed.same_connectors(&DefaultBoard::<3, 3>::default_board()));
+    }
+
+    #[test]
+    pub fn test_from_connectors_str_errors() {
+        let gems = || Gem::pair_from_num(0);
+        assert_eq!(
+            Board::from_connectors_str("─│└\n┌x┘\n┴├┬", gems).unwrap_err(),
+            ConnectorsError::InvalidChar('x', (1, 1))
+        );
+        assert_eq!(
+            Board::from_connectors_str("─│└\n┌┐\n┴├┬", gems).unwrap_err(),
+            ConnectorsError::RaggedRow(1, 2, 3)
+        );
+        assert_eq!(
+            Board::from_connectors_str(" \n", gems).unwrap_err(),
+            ConnectorsError::Empty
+        );
+    }
+
+    #[test]
+    pub fn test_positions_with_gem() {
+        let b: Board = DefaultBoard::<7, 7>::default_board();
+        let gem = Gem::from_num(5);
+        let positions = b.positions_with_gem(gem);
+        assert_eq!(positions, vec![(5, 0)]);
+        assert!(positions.iter().all(|&pos| {
+            let gems = &b[pos].gems;
+            gems.0 == gem || gems.1 == gem
+        }));
+
+        // every tile on the default board shares its first gem
+        assert_eq!(b.positions_with_gem(Gem::from_num(0)).len(), 49);
+
+        // the spare tile's gems are not on the grid
+        assert!(b.positions_with_gem(b.spare.gems.0).is_empty());
+        assert!(b.positions_with_gem(Gem::from_num(60)).is_empty());
+    }
+
+    #[test]
+    pub fn test_spare_after_rotations() {
+        let mut board = Board::default();
+        assert_eq!(board.spare.connector, Crossroads);
+        assert!((0..8).all(|n| board.spare_after_rotations(n) == Crossroads));
+
+        // the same slides as `test_rotate_spare` in `state`
+        for _ in 0..2 {
+            let slide = board.new_slide(0, North).unwrap();
+            board.slide_and_insert(slide).unwrap();
+        }
+        assert_eq!(board.spare.connector, Fork(East));
+
+        assert_eq!(board.spare_after_rotations(0), Fork(East));
+        assert_eq!(board.spare_after_rotations(1), Fork(North));
+        assert_eq!(board.spare_after_rotations(3), Fork(South));
+        assert_eq!(board.spare_after_rotations(8), Fork(East));
+        assert_eq!(board.spare_after_rotations(9), Fork(North));
+        // the spare itself is not rotated
+        assert_eq!(board.spare.connector, Fork(East));
+    }
+
+    #[test]
+    pub fn test_has_unique_gems() {
+        let board = Board::default();
+        assert!(board.has_unique_gems());
+
+        let mut duplicate = board.clone();
+        duplicate.grid[(1, 0)].gems = duplicate.grid[(0, 0)].gems;
+        assert!(!duplicate.has_unique_gems());
+
+        // gem pairs are unordered
+        let mut swapped = board.clone();
+        let UnorderedPair(first, second) = board.grid[(0, 0)].gems;
+        swapped.grid[(6, 6)].gems = UnorderedPair(second, first);
+        assert!(!swapped.has_unique_gems());
+
+        let mut spare = board.clone();
+        spare.spare.gems = board.grid[(3, 3)].gems;
+        assert!(!spare.has_unique_gems());
+    }
+
+    #[test]
+    pub fn test_connectivity_map() {
+        let board = Board::default();
+        let map = board.connectivity_map();
+        assert_eq!(map.len(), board.num_rows());
+        assert_eq!(map[0].len(), board.num_cols());
+
+        assert_eq!(
+            board[(0, 0)].connector,
+            Path(crate::tile::PathOrientation::Horizontal)
+        );
+        assert_eq!(map[(0, 0)], 2);
+        assert_eq!(board[(2, 0)].connector, Corner(North));
+        assert_eq!(map[(2, 0)], 2);
+        assert_eq!(board[(6, 0)].connector, Fork(North));
+        assert_eq!(map[(6, 0)], 3);
+        assert_eq!(board[(3, 1)].connector, Crossroads);
+        assert_eq!(map[(3, 1)], 4);
+
+        for pos in board.grid.iter_positions() {
+            assert_eq!(
+                map[pos] as usize,
+                board[pos].connector.open_directions().len()
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_num_components() {
+        let board = Board::from_connectors_str("┌┐\n└┘", || Gem::pair_from_num(0)).unwrap();
+        assert_eq!(board.num_components(), 1);
+
+        let board = Board::from_connectors_str("││\n││", || Gem::pair_from_num(0)).unwrap();
+        assert_eq!(board.num_components(), 2);
+
+        let board = Board::from_connectors_str("─│\n│─", || Gem::pair_from_num(0)).unwrap();
+        assert_eq!(board.num_components(), 4);
+    }
+
+    #[test]
+    pub fn test_random_connected() {
+        for seed in 0..10 {
+            let mut rng = ChaChaRng::seed_from_u64(seed);
+            let board = Board::random_connected::<7, 7>(&mut rng).unwrap();
+            assert_eq!(board.num_rows(), 7);
+            assert_eq!(board.num_cols(), 7);
+            assert_eq!(board.num_components(), 1);
+
+            let board = Board::random_connected::<5, 3>(&mut rng).unwrap();
+            assert_eq!(board.num_rows(), 3);
+            assert_eq!(board.num_cols(), 5);
+            assert_eq!(board.num_components(), 1);
+        }
+
+        let board = Board::random_connected::<7, 7>(&mut ChaChaRng::seed_from_u64(3));
+        assert_eq!(
+            board,
+            Board::random_connected::<7, 7>(&mut ChaChaRng::seed_from_u64(3))
+        );
+
+        // `SequentialGems` runs out of unique pairs before the 103rd tile
+        assert_eq!(
+            Board::random_connected::<11, 10>(&mut ChaChaRng::seed_from_u64(0)),
+            Err(DuplicateGems(Gem::pair_from_num(1)))
+        );
+    }
+
+    #[test]
+    pub fn test_random_connected_with_gems() {
+        let mut rng = ChaChaRng::seed_from_u64(0);
+        let board =
+            Board::random_connected_with_gems::<7, 7>(&mut rng, SequentialGems::default()).unwrap();
+        let default_board = Board::default();
+        assert!(board
+            .grid
+            .iter_positions()
+            .all(|pos| board[pos].gems == default_board[pos].gems));
+
+        let gems = RandomGems::new(ChaChaRng::seed_from_u64(1));
+        let board = Board::random_connected_with_gems::<7, 7>(&mut rng, gems).unwrap();
+        let pairs = board
+            .grid
+            .iter_positions()
+            .map(|pos| board[pos].gems)
+            .chain([board.spare.gems])
+            .collect::<HashSet<_>>();
+        assert_eq!(pairs.len(), 50);
+        assert_eq!(board.num_components(), 1);
+
+        let same = || Gem::pair_from_num(3);
+        assert_eq!(
+            Board::random_connected_with_gems::<7, 7>(&mut rng, same),
+            Err(DuplicateGems(Gem::pair_from_num(3)))
+        );
+        let mut num = 0;
+        let cycling = || {
+            num += 1;
+            Gem::pair_from_num(num % 5)
+        };
+        assert_eq!(
+            Board::random_connected_with_gems::<3, 3>(&mut rng, cycling),
+            Err(DuplicateGems(Gem::pair_from_num(1)))
+        );
+    }
+
+    #[test]
+    pub fn test_apply_slides() {
+        let slides = [
+            Slide::new_unchecked(0, South),
+            Slide::new_unchecked(0, East),
+            Slide::new_unchecked(2, West),
+        ];
+        let mut b: Board = DefaultBoard::<3, 3>::default_board();
+        let mut expected = b.clone();
+        for slide in slides {
+            expected.slide_and_insert(slide).unwrap();
+        }
+        b.apply_slides(&slides).unwrap();
+        assert_eq!(b, expected);
+
+        // the invalid slide comes last, so the valid ones before it must be undone
+        let original: Board = DefaultBoard::<3, 3>::default_board();
+        let mut b = original.clone();
+        assert_eq!(
+            b.apply_slides(&[
+                Slide::new_unchecked(0, South),
+                Slide::new_unchecked(2, West),
+                Slide::new_unchecked(1, North),
+            ]),
+            Err(SlideError::ImmovableLine(1))
+        );
+        assert_eq!(b, original);
+
+        // indices past the edge of the board are out of range
+        assert_eq!(
+            b.apply_slides(&[
+                Slide::new_unchecked(0, South),
+                Slide::new_unchecked(4, East)
+            ]),
+            Err(SlideError::IndexOutOfRange(4, 3))
+        );
+        assert_eq!(b, original);
+
+        let mut b = original.clone();
+        assert!(b.apply_slides(&[]).is_ok());
+        assert_eq!(b, original);
+    }
 }
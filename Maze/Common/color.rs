@@ -1,6 +1,13 @@
+use std::cmp::Ordering;
+
 use hex::ToHex;
+use serde::{Deserialize, Serialize};
+
+use crate::json::JsonColor;
 
-#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+/// (De)serializes as its `name`, following the same rules as `JsonColor`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(try_from = "JsonColor", into = "JsonColor")]
 pub struct Color {
     /// The original name of the color.
     /// Is either the name of a color, like "red", or the Hex Color code for that color
@@ -10,6 +17,38 @@ pub struct Color {
     pub code: (u8, u8, u8),
 }
 
+impl Color {
+    /// Returns the color of text that is easiest to read on top of `self`: black for light
+    /// colors and white for dark colors, based on the perceived brightness of `self.code`.
+    pub fn best_text_color(&self) -> (u8, u8, u8) {
+        let (r, g, b) = self.code;
+        let brightness = 299 * r as u32 + 587 * g as u32 + 114 * b as u32;
+        if brightness > 128 * 1000 {
+            (0, 0, 0)
+        } else {
+            (255, 255, 255)
+        }
+    }
+
+    /// Returns the perceived brightness of `self.code`, from `0.0` for black to `1.0` for white,
+    /// using the same weights as `best_text_color`.
+    #[must_use]
+    pub fn luminance(&self) -> f32 {
+        let (r, g, b) = self.code;
+        (0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32) / 255.0
+    }
+
+    /// Orders colors from darkest to lightest by `luminance`. Colors with the same luminance are
+    /// ordered by their code, then by their name, so this is a total order.
+    #[must_use]
+    pub fn cmp_by_luminance(&self, other: &Self) -> Ordering {
+        self.luminance()
+            .total_cmp(&other.luminance())
+            .then_with(|| self.code.cmp(&other.code))
+            .then_with(|| self.name.cmp(&other.name))
+    }
+}
+
 impl From<(u8, u8, u8)> for Color {
     fn from((r, g, b): (u8, u8, u8)) -> Self {
         Color {
@@ -75,3 +114,87 @@ impl From<ColorName> for Color {
         }
     }
 }
+
+#[cfg(test)]
+mod color_tests {
+    use super::*;
+
+    #[test]
+    fn test_best_text_color() {
+        let white = (255, 255, 255);
+        let black = (0, 0, 0);
+        assert_eq!(Color::from(ColorName::Black).best_text_color(), white);
+        assert_eq!(Color::from(ColorName::Blue).best_text_color(), white);
+        assert_eq!(Color::from(ColorName::Purple).best_text_color(), white);
+        assert_eq!(Color::from((40, 40, 40)).best_text_color(), white);
+
+        assert_eq!(Color::from(ColorName::White).best_text_color(), black);
+        assert_eq!(Color::from(ColorName::Yellow).best_text_color(), black);
+        assert_eq!(Color::from(ColorName::Pink).best_text_color(), black);
+        assert_eq!(Color::from(ColorName::Green).best_text_color(), black);
+    }
+
+    #[test]
+    fn test_cmp_by_luminance() {
+        assert_eq!(Color::from(ColorName::Black).luminance(), 0.0);
+        assert!((Color::from(ColorName::White).luminance() - 1.0).abs() < 1e-6);
+
+        let sorted = |colors: Vec<Color>| {
+            let mut colors = colors;
+            colors.sort_by(Color::cmp_by_luminance);
+            colors.into_iter().map(|c| c.name).collect::<Vec<_>>()
+        };
+        let colors = vec![
+            ColorName::White.into(),
+            ColorName::Yellow.into(),
+            ColorName::Red.into(),
+            ColorName::Black.into(),
+            ColorName::Pink.into(),
+            ColorName::Green.into(),
+            ColorName::Blue.into(),
+            ColorName::Purple.into(),
+            ColorName::Orange.into(),
+        ];
+        let expected = vec![
+            "black", "blue", "purple", "red", "green", "orange", "pink", "yellow", "white",
+        ];
+        assert_eq!(sorted(colors.clone()), expected);
+        let mut reversed = colors;
+        reversed.reverse();
+        assert_eq!(sorted(reversed), expected);
+
+        // equally bright colors are still ordered
+        let red = Color::from(ColorName::Red);
+        let also_red = Color {
+            name: "FF0000".to_string(),
+            code: red.code,
+        };
+        assert_eq!(red.cmp_by_luminance(&also_red), Ordering::Greater);
+        assert_eq!(red.cmp_by_luminance(&red.clone()), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_color_serde() {
+        #[derive(Debug, Deserialize, Serialize)]
+        struct Colored {
+            color: Color,
+        }
+
+        let colored: Colored = serde_json::from_str(r#"{ "color": "red" }"#).unwrap();
+        assert_eq!(colored.color, Color::from(ColorName::Red));
+        assert_eq!(
+            serde_json::to_string(&colored).unwrap(),
+            r#"{"color":"red"}"#
+        );
+
+        let colored: Colored = serde_json::from_str(r#"{ "color": "FF00A0" }"#).unwrap();
+        assert_eq!(colored.color, Color::from((255, 0, 160)));
+        assert_eq!(
+            serde_json::to_string(&colored).unwrap(),
+            r#"{"color":"FF00A0"}"#
+        );
+
+        assert!(serde_json::from_str::<Colored>(r#"{ "color": "mauve" }"#).is_err());
+        assert!(serde_json::from_str::<Colored>(r#"{ "color": "ff00a0" }"#).is_err());
+    }
+}
@@ -1,10 +1,11 @@
 #![allow(non_camel_case_types)]
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use convert_case::{Case, Casing};
 use egui_extras::RetainedImage;
 use include_dir::{include_dir, Dir};
 use lazy_static::lazy_static;
+use rand::Rng;
 use serde::Deserialize;
 use serde::Serialize;
 use unordered_pair::UnorderedPair;
@@ -129,6 +130,9 @@ impl Gem {
         UnorderedPair(left, right)
     }
 
+    /// Returns the `Gem` at index `num % NUM_GEMS`, wrapping around for `num >= NUM_GEMS`.
+    ///
+    /// Use `Gem::try_from_index` if `num` is not expected to wrap.
     pub fn from_num(num: usize) -> Gem {
         use Gem::*;
         match num % NUM_GEMS {
@@ -237,6 +241,75 @@ impl Gem {
             _ => unreachable!(),
         }
     }
+
+    /// Returns the `Gem` at index `num`, or `None` if `num >= NUM_GEMS`.
+    pub fn try_from_index(num: usize) -> Option<Gem> {
+        (num < NUM_GEMS).then(|| Gem::from_num(num))
+    }
+}
+
+/// Hands out the gem pairs for the tiles of a generated `Board`, one pair per call.
+///
+/// Any `FnMut() -> UnorderedPair<Gem>` closure is a `GemSource`.
+pub trait GemSource {
+    fn next_pair(&mut self) -> UnorderedPair<Gem>;
+}
+
+impl<F: FnMut() -> UnorderedPair<Gem>> GemSource for F {
+    fn next_pair(&mut self) -> UnorderedPair<Gem> {
+        self()
+    }
+}
+
+/// Hands out `Gem::pair_from_num(0)`, `Gem::pair_from_num(1)`, ... which are the gems of the
+/// tiles of `DefaultBoard::default_board`.
+#[derive(Debug, Default, Clone)]
+pub struct SequentialGems {
+    next: usize,
+}
+
+impl GemSource for SequentialGems {
+    fn next_pair(&mut self) -> UnorderedPair<Gem> {
+        let pair = Gem::pair_from_num(self.next);
+        self.next += 1;
+        pair
+    }
+}
+
+/// Hands out random gem pairs, never handing out the same pair twice.
+#[derive(Debug, Clone)]
+pub struct RandomGems<R> {
+    rng: R,
+    used: HashSet<UnorderedPair<Gem>>,
+}
+
+impl<R: Rng> RandomGems<R> {
+    pub fn new(rng: R) -> Self {
+        RandomGems {
+            rng,
+            used: HashSet::new(),
+        }
+    }
+}
+
+impl<R: Rng> GemSource for RandomGems<R> {
+    /// # Panics
+    /// Panics if every possible pair of gems has already been handed out.
+    fn next_pair(&mut self) -> UnorderedPair<Gem> {
+        assert!(
+            self.used.len() < NUM_GEMS * (NUM_GEMS + 1) / 2,
+            "every pair of gems has been handed out"
+        );
+        loop {
+            let pair = UnorderedPair(
+                Gem::from_num(self.rng.gen_range(0..NUM_GEMS)),
+                Gem::from_num(self.rng.gen_range(0..NUM_GEMS)),
+            );
+            if self.used.insert(pair) {
+                return pair;
+            }
+        }
+    }
 }
 
 macro_rules! gem_insert {
@@ -365,3 +438,18 @@ lazy_static! {
         m
     };
 }
+
+#[cfg(test)]
+mod gem_tests {
+    use super::*;
+
+    #[test]
+    pub fn test_try_from_index() {
+        assert_eq!(Gem::try_from_index(0), Some(Gem::AlexandritePearShape));
+        assert_eq!(Gem::try_from_index(101), Some(Gem::Zoisite));
+        assert_eq!(Gem::try_from_index(102), None);
+        assert_eq!(Gem::try_from_index(usize::MAX), None);
+        assert_eq!(Gem::from_num(102), Gem::AlexandritePearShape);
+        assert!((0..NUM_GEMS).all(|num| Gem::try_from_index(num) == Some(Gem::from_num(num))));
+    }
+}
@@ -1,5 +1,7 @@
 use std::ops::{Deref, DerefMut, Index, IndexMut};
 
+use thiserror::Error;
+
 /// Type alias for Positions on the Board
 /// A position is a tuple of (x, y)
 /// where:
@@ -8,16 +10,66 @@ use std::ops::{Deref, DerefMut, Index, IndexMut};
 pub type Position = (usize, usize);
 
 /// Returns the squared euclidian distance between two `Position`s
+///
+/// Saturates at `usize::MAX` instead of overflowing on very distant `Position`s.
 #[must_use]
 pub fn squared_euclidian_distance(p1: &Position, p2: &Position) -> usize {
-    ((p1.0 as isize - p2.0 as isize).pow(2) + (p1.1 as isize - p2.1 as isize).pow(2)) as usize
+    checked_squared_euclidian_distance(p1, p2).unwrap_or(usize::MAX)
+}
+
+/// Returns the squared euclidian distance between two `Position`s, or `None` if it does not fit
+/// in a `usize`
+#[must_use]
+pub fn checked_squared_euclidian_distance(p1: &Position, p2: &Position) -> Option<usize> {
+    let dx = p1.0.abs_diff(p2.0);
+    let dy = p1.1.abs_diff(p2.1);
+    dx.checked_mul(dx)?.checked_add(dy.checked_mul(dy)?)
+}
+
+/// The error returned by `Grid::from_flat` when the data does not fill the requested dimensions
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("{len} cells cannot fill a {cols}x{rows} grid!")]
+pub struct GridSizeMismatch {
+    pub len: usize,
+    pub cols: usize,
+    pub rows: usize,
 }
 
 /// Represents a wrapper type for a 2D array with added functionality
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Grid<T>(Box<[Box<[T]>]>);
 
 impl<T> Grid<T> {
+    /// Constructs a `Grid` with `cols` columns and `rows` rows from `data` in row-major order.
+    ///
+    /// # Errors
+    /// Returns an error if `data` does not have exactly `cols * rows` elements, or if `cols * rows`
+    /// overflows.
+    ///
+    /// # Examples
+    /// ```
+    /// use common::grid::Grid;
+    /// let g = Grid::from_flat(vec![1, 2, 3, 4, 5, 6], 3, 2).unwrap();
+    /// assert_eq!(g[(2, 1)], 6);
+    /// assert!(Grid::from_flat(vec![1, 2, 3], 2, 2).is_err());
+    /// ```
+    pub fn from_flat(data: Vec<T>, cols: usize, rows: usize) -> Result<Self, GridSizeMismatch> {
+        if cols.checked_mul(rows) != Some(data.len()) {
+            return Err(GridSizeMismatch {
+                len: data.len(),
+                cols,
+                rows,
+            });
+        }
+
+        let mut data = data.into_iter();
+        Ok(Grid(
+            (0..rows)
+                .map(|_| data.by_ref().take(cols).collect())
+                .collect(),
+        ))
+    }
+
     /// Rotates the row at `index` left one time
     pub fn rotate_left(&mut self, index: usize) {
         self[index].rotate_left(1);
@@ -39,6 +91,23 @@ impl<T> Grid<T> {
         }
     }
 
+    /// Returns every `Position` in this `Grid` in row-major order
+    pub fn iter_positions(&self) -> impl Iterator<Item = Position> + '_ {
+        self.iter()
+            .enumerate()
+            .flat_map(|(row, cells)| (0..cells.len()).map(move |col| (col, row)))
+    }
+
+    /// Returns every cell in this `Grid` paired with its `Position`, in row-major order
+    pub fn enumerate(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.iter().enumerate().flat_map(|(row, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .map(move |(col, cell)| ((col, row), cell))
+        })
+    }
+
     /// Rotates the column at `index` down one time
     pub fn rotate_down(&mut self, col_num: usize) {
         for row_index in (0..(self.len() - 1)).rev() {
@@ -184,6 +253,85 @@ impl<T> IndexMut<Position> for Grid<T> {
 mod grid_tests {
     use super::*;
 
+    #[test]
+    pub fn test_grid_iter_positions() {
+        let g = Grid::from([['a', 'b'], ['c', 'd'], ['e', 'f']]);
+        assert_eq!(
+            g.iter_positions().collect::<Vec<_>>(),
+            vec![(0, 0), (1, 0), (0, 1), (1, 1), (0, 2), (1, 2)]
+        );
+        assert_eq!(
+            g.enumerate().collect::<Vec<_>>(),
+            vec![
+                ((0, 0), &'a'),
+                ((1, 0), &'b'),
+                ((0, 1), &'c'),
+                ((1, 1), &'d'),
+                ((0, 2), &'e'),
+                ((1, 2), &'f'),
+            ]
+        );
+        for (pos, cell) in g.enumerate() {
+            assert_eq!(&g[pos], cell);
+        }
+    }
+
+    #[test]
+    pub fn test_squared_euclidian_distance() {
+        assert_eq!(squared_euclidian_distance(&(0, 0), &(0, 0)), 0);
+        assert_eq!(squared_euclidian_distance(&(1, 1), &(4, 5)), 25);
+        assert_eq!(squared_euclidian_distance(&(4, 5), &(1, 1)), 25);
+        assert_eq!(squared_euclidian_distance(&(6, 0), &(0, 6)), 72);
+        assert_eq!(
+            checked_squared_euclidian_distance(&(1, 1), &(4, 5)),
+            Some(25)
+        );
+
+        let far = (usize::MAX, usize::MAX);
+        assert_eq!(checked_squared_euclidian_distance(&(0, 0), &far), None);
+        assert_eq!(squared_euclidian_distance(&(0, 0), &far), usize::MAX);
+        assert_eq!(squared_euclidian_distance(&far, &far), 0);
+        assert_eq!(
+            squared_euclidian_distance(&(0, 0), &(usize::MAX, 0)),
+            usize::MAX
+        );
+    }
+
+    #[test]
+    pub fn test_grid_from_flat() {
+        let g = Grid::from_flat((0..12).collect(), 3, 4).unwrap();
+        assert_eq!(g.len(), 4);
+        assert_eq!(g[0].len(), 3);
+        assert_eq!(
+            g,
+            Grid::from([[0, 1, 2], [3, 4, 5], [6, 7, 8], [9, 10, 11]])
+        );
+        assert_eq!(g[(2, 0)], 2);
+        assert_eq!(g[(0, 3)], 9);
+        assert_eq!(g[(1, 2)], 7);
+
+        assert_eq!(
+            Grid::from_flat((0..11).collect::<Vec<usize>>(), 3, 4),
+            Err(GridSizeMismatch {
+                len: 11,
+                cols: 3,
+                rows: 4
+            })
+        );
+        assert!(Grid::from_flat(vec![(); 13], 3, 4).is_err());
+        assert_eq!(Grid::<()>::from_flat(vec![], 0, 2).unwrap().len(), 2);
+
+        // `cols * rows` wraps around to 0 here
+        assert_eq!(
+            Grid::<()>::from_flat(vec![], usize::MAX / 2 + 1, 2),
+            Err(GridSizeMismatch {
+                len: 0,
+                cols: usize::MAX / 2 + 1,
+                rows: 2
+            })
+        );
+    }
+
     #[test]
     pub fn test_grid_rotate_left() {
         let mut g = Grid::from([
@@ -1,7 +1,7 @@
-use std::{cmp::Ordering, collections::HashSet, hash::Hash};
+use std::{cmp::Ordering, collections::HashSet, hash::Hash, io::Read};
 
 use aliri_braid::braid;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use thiserror::Error;
 use unordered_pair::UnorderedPair;
 
@@ -58,6 +58,8 @@ pub enum JsonError {
     GoalMoveableTile(Vec<Position>),
     #[error("The player has been assigned a goal that is in the list of remaining goals")]
     DuplicateAssignedGoals,
+    #[error("Expected the number of goals reached by {0} players, but got {1}")]
+    GoalsReachedMismatch(usize, usize),
     #[error("{0:?} is/are out of bounds on the given board")]
     PositionOutOfBounds(Vec<Position>),
     #[error("{0:?} is not a valid slide for this board")]
@@ -66,6 +68,8 @@ pub enum JsonError {
     InvalidColor(String),
     #[error("Invalid JsonDegreee {0}")]
     InvalidDegree(usize),
+    #[error("There is no strategy named {0:?}")]
+    UnknownStrategy(String),
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -162,9 +166,11 @@ impl From<Treasure> for UnorderedPair<Gem> {
     }
 }
 
+/// Always puts the smaller `Gem` first, so the same pair of `Gem`s is serialized the same way no
+/// matter the order the `UnorderedPair` stores them in.
 impl From<UnorderedPair<Gem>> for Treasure {
     fn from(val: UnorderedPair<Gem>) -> Self {
-        Treasure(val.0, val.1)
+        Treasure(val.0.min(val.1), val.0.max(val.1))
     }
 }
 
@@ -218,6 +224,24 @@ where
     iter.into_iter().all(move |x| unique.insert(x))
 }
 
+/// Lazily deserializes a stream of JSON values from `reader`.
+///
+/// Every valid value before a malformed one (invalid JSON, invalid UTF-8, or a value cut off at
+/// EOF) is yielded before the error. The stream ends after the first error, since the reader
+/// cannot be resynchronized after it.
+pub fn json_stream<T: DeserializeOwned>(
+    reader: impl Read,
+) -> impl Iterator<Item = serde_json::Result<T>> {
+    let mut errored = false;
+    serde_json::Deserializer::from_reader(reader)
+        .into_iter::<T>()
+        .take_while(move |value| {
+            let keep_going = !errored;
+            errored |= value.is_err();
+            keep_going
+        })
+}
+
 impl TryFrom<(JsonBoard, JsonTile)> for Board {
     type Error = JsonError;
 
@@ -297,6 +321,8 @@ impl From<Board> for (JsonBoard, JsonTile) {
 /// # Constraints
 /// - `plmt` is a non-empty array
 /// - no two `JsonPlayer`s will have the same `JsonColor`
+///
+/// Unknown fields are ignored, so clients can attach extra metadata to a state.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct JsonState {
     pub board: JsonBoard,
@@ -332,6 +358,14 @@ where
             return Err(JsonError::NonUniqueHomes);
         }
 
+        // a home past the edge of the board is out of bounds, not on the spare tile
+        homes.iter().try_for_each(|home| {
+            board
+                .in_bounds(home)
+                .then_some(())
+                .ok_or_else(|| JsonError::PositionOutOfBounds(vec![*home]))
+        })?;
+
         let possible_homes = board.possible_homes().collect::<Vec<_>>();
         let invalid_homes = player_info
             .iter()
@@ -343,16 +377,6 @@ where
             return Err(JsonError::HomeMoveableTile(invalid_homes));
         }
 
-        homes
-            .iter()
-            .fold(Ok(()), |acc: Result<(), JsonError>, home| {
-                board
-                    .in_bounds(home)
-                    .then_some(())
-                    .ok_or_else(|| JsonError::PositionOutOfBounds(vec![*home]))?;
-                acc
-            })?;
-
         let previous_slide = jstate.last.into();
         if let Some(slide) = previous_slide {
             if !board.valid_slide(slide) {
@@ -414,7 +438,7 @@ impl From<Tile> for JsonTile {
 }
 
 /// Describes a player's current location, the
-/// location of its home, and the color of its avatar.
+/// location of its home, and the color of its avatar. Unknown fields are ignored.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct JsonPlayer {
     pub current: Coordinate,
@@ -489,14 +513,46 @@ impl From<Color> for JsonColor {
 /// Specifies the last sliding action that an actor
 /// performed; `None` indicates that no sliding action has been performed yet.
 #[derive(Debug, Deserialize, Serialize)]
-pub struct JsonAction(Option<(Index, JsonDirection)>);
+pub struct JsonAction(Option<JsonSlideAction>);
+
+/// A slide in a `JsonAction`, which is either `[index, direction]` or, for protocols that also
+/// send the rotation of the spare tile, `[index, direction, degree]`.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum JsonSlideAction {
+    Rotated(Index, JsonDirection, JsonDegree),
+    Slid(Index, JsonDirection),
+}
+
+impl JsonAction {
+    /// Creates a `JsonAction` for `slide` that also records the spare tile being rotated
+    /// `rotations` times.
+    pub fn with_rotations(slide: Slide, rotations: usize) -> Self {
+        JsonAction(Some(JsonSlideAction::Rotated(
+            Index(slide.index),
+            slide.direction.into(),
+            JsonDegree(rotations % 4 * 90),
+        )))
+    }
+
+    /// Returns the degree the spare tile was rotated by, if this `JsonAction` records it
+    pub fn degree(&self) -> Option<usize> {
+        match &self.0 {
+            Some(JsonSlideAction::Rotated(_, _, degree)) => Some(degree.0),
+            _ => None,
+        }
+    }
+}
 
 impl From<JsonAction> for Option<Slide> {
     fn from(ja: JsonAction) -> Self {
-        let jslide = ja.0?;
+        let (index, direction) = match ja.0? {
+            JsonSlideAction::Rotated(index, direction, _) => (index, direction),
+            JsonSlideAction::Slid(index, direction) => (index, direction),
+        };
         Some(Slide {
-            index: jslide.0 .0,
-            direction: jslide.1.into(),
+            index: index.0,
+            direction: direction.into(),
         })
     }
 }
@@ -505,7 +561,9 @@ impl From<Option<Slide>> for JsonAction {
     fn from(s: Option<Slide>) -> Self {
         match s {
             None => JsonAction(None),
-            Some(Slide { index, direction }) => JsonAction(Some((Index(index), direction.into()))),
+            Some(Slide { index, direction }) => {
+                JsonAction(Some(JsonSlideAction::Slid(Index(index), direction.into())))
+            }
         }
     }
 }
@@ -571,6 +629,36 @@ impl TryFrom<JsonDegree> for usize {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_json_action_forms() {
+        let action: JsonAction = serde_json::from_str("[0, \"LEFT\", 90]").unwrap();
+        assert_eq!(action.degree(), Some(90));
+        assert_eq!(serde_json::to_string(&action).unwrap(), "[0,\"LEFT\",90]");
+        let slide: Option<Slide> = action.into();
+        assert_eq!(slide, Some(Slide::new_unchecked(0, CompassDirection::West)));
+
+        let action: JsonAction = serde_json::from_str("[2, \"UP\"]").unwrap();
+        assert_eq!(action.degree(), None);
+        assert_eq!(serde_json::to_string(&action).unwrap(), "[2,\"UP\"]");
+        let slide: Option<Slide> = action.into();
+        assert_eq!(
+            slide,
+            Some(Slide::new_unchecked(2, CompassDirection::North))
+        );
+
+        let action: JsonAction = serde_json::from_str("null").unwrap();
+        assert_eq!(action.degree(), None);
+        assert_eq!(serde_json::to_string(&action).unwrap(), "null");
+        assert_eq!(Option::<Slide>::from(action), None);
+
+        let action =
+            JsonAction::with_rotations(Slide::new_unchecked(4, CompassDirection::South), 3);
+        assert_eq!(serde_json::to_string(&action).unwrap(), "[4,\"DOWN\",270]");
+
+        assert!(serde_json::from_str::<JsonAction>("[0]").is_err());
+        assert!(serde_json::from_str::<JsonAction>("[0, \"LEFT\", 90, 1]").is_err());
+    }
+
     #[test]
     fn test_name_validator() {
         assert!(serde_json::from_str::<Name>("\"Bill\"").is_ok());
@@ -578,4 +666,97 @@ mod tests {
         assert!(serde_json::from_str::<Name>("\"_\"").is_err());
         assert!(serde_json::from_str::<Name>("\"BartholomewRobertsonTheThird\"").is_err());
     }
+
+    #[test]
+    fn test_state_ignores_unknown_fields() {
+        let state: State<PlayerInfo> = State {
+            player_info: vec![PlayerInfo {
+                current: (0, 0),
+                home: (1, 1),
+                color: ColorName::Red.into(),
+            }]
+            .into(),
+            ..Default::default()
+        };
+        let expected = serde_json::to_value(JsonState::from(state)).unwrap();
+
+        let mut json = expected.clone();
+        json["plmt"][0]
+            .as_object_mut()
+            .unwrap()
+            .insert("notes".into(), "joined late".into());
+        json.as_object_mut()
+            .unwrap()
+            .insert("notes".into(), "from a newer client".into());
+
+        let parsed: State<PlayerInfo> = serde_json::from_value::<JsonState>(json)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(JsonState::from(parsed)).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_state_home_past_edge() {
+        let state: State<PlayerInfo> = State {
+            player_info: vec![PlayerInfo {
+                current: (1, 1),
+                home: (1, 1),
+                color: ColorName::Red.into(),
+            }]
+            .into(),
+            ..Default::default()
+        };
+        let mut jstate = JsonState::from(state);
+        jstate.plmt[0].home = Coordinate {
+            row: Index(1),
+            column: Index(7),
+        };
+
+        assert!(matches!(
+            State::<PlayerInfo>::try_from(jstate),
+            Err(JsonError::PositionOutOfBounds(positions)) if positions == vec![(7, 1)]
+        ));
+    }
+
+    #[test]
+    fn test_treasure_canonical_order() {
+        let board = Board::default();
+        let mut swapped = board.clone();
+        let UnorderedPair(first, second) = swapped.grid[(1, 0)].gems;
+        swapped.grid[(1, 0)].gems = UnorderedPair(second, first);
+        assert_ne!(board.grid[(1, 0)].gems.0, swapped.grid[(1, 0)].gems.0);
+
+        let to_json = |board: Board| {
+            let (jboard, _): (JsonBoard, JsonTile) = board.into();
+            serde_json::to_string(&jboard).unwrap()
+        };
+        assert_eq!(to_json(board), to_json(swapped));
+    }
+
+    #[test]
+    fn test_json_stream() {
+        let input = r#"{"row#": 0, "column#": 1} {"row#": 2, "column#": 3} garbage"#;
+        let mut stream = json_stream::<Coordinate>(input.as_bytes());
+        assert_eq!(stream.next().unwrap().map(Position::from).unwrap(), (1, 0));
+        assert_eq!(stream.next().unwrap().map(Position::from).unwrap(), (3, 2));
+        assert!(stream.next().unwrap().is_err());
+        assert!(stream.next().is_none());
+
+        // a value cut off at EOF still yields everything before it
+        let input = r#"{"row#": 0, "column#": 1} {"row#": 2, "col"#;
+        let mut stream = json_stream::<Coordinate>(input.as_bytes());
+        assert!(stream.next().unwrap().is_ok());
+        assert!(stream.next().unwrap().unwrap_err().is_eof());
+        assert!(stream.next().is_none());
+
+        // invalid UTF-8 is an error, not a panic
+        let input = b"{\"row#\": 0, \"column#\": 1} \"\xff\"";
+        let mut stream = json_stream::<serde_json::Value>(&input[..]);
+        assert!(stream.next().unwrap().is_ok());
+        assert!(stream.next().unwrap().is_err());
+    }
 }
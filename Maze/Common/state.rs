@@ -1,11 +1,18 @@
-use std::collections::VecDeque;
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt,
+    hash::{Hash, Hasher},
+};
 
+use itertools::Itertools;
 use thiserror::Error;
 
 use crate::{
     board::{self, Board, Slide},
     color::Color,
-    grid::Position,
+    grid::{squared_euclidian_distance, Position},
+    json::has_unique_elements,
+    tile::Tile,
 };
 
 #[derive(Debug, Error)]
@@ -16,8 +23,18 @@ pub enum StateError {
     PositionUnreachable(Position),
     #[error("No more players are in the game!")]
     NoPlayersLeft,
+    #[error("There is no player at index {0}!")]
+    NoSuchPlayer(usize),
     #[error("The provided move was invalid")]
     InvalidMove,
+    #[error("The home of the {} player is on a moveable tile!", .0.name)]
+    HomeMoveableTile(Color),
+    #[error("The goal of the {} player is on a moveable tile!", .0.name)]
+    GoalMoveableTile(Color),
+    #[error("Every player must have a unique color!")]
+    NonUniqueColors,
+    #[error("More than one slide was made since the snapshot was taken!")]
+    SnapshotMismatch,
     #[error(transparent)]
     BoardError(#[from] board::OutOfBounds),
 }
@@ -40,6 +57,57 @@ pub trait PrivatePlayerInfo: PublicPlayerInfo {
     fn goal(&self) -> Position;
     fn get_goals_reached(&self) -> u64;
     fn inc_goals_reached(&mut self);
+    /// Records that this player reached `goal`, which also increments their goals reached.
+    fn collect_goal(&mut self, goal: Position);
+    /// The goals collected with `collect_goal`, in the order they were collected.
+    fn goals_history(&self) -> &[Position];
+}
+
+/// A `Hasher` implementing 64 bit FNV-1a. Unlike `DefaultHasher`, the hashes it produces are the
+/// same on every platform and Rust version, so they can be stored and compared across runs.
+#[derive(Debug, Clone, Copy)]
+pub struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
+impl StableHasher {
+    /// Writes `num` as little endian bytes, regardless of the platform's `usize` width
+    pub fn write_len(&mut self, num: usize) {
+        self.write(&(num as u64).to_le_bytes());
+    }
+
+    /// Writes `s` followed by a terminator, so consecutive strings cannot run together
+    pub fn write_terminated(&mut self, s: &str) {
+        self.write(s.as_bytes());
+        self.write(&[0xff]);
+    }
+
+    /// Writes the home, position, goal, color name, and number of goals reached of `info`
+    pub fn write_player_info(&mut self, info: &impl PrivatePlayerInfo) {
+        for pos in [info.home(), info.position(), info.goal()] {
+            self.write_len(pos.0);
+            self.write_len(pos.1);
+        }
+        self.write_terminated(&info.color().name);
+        self.write(&info.get_goals_reached().to_le_bytes());
+    }
 }
 
 /// Represents a Player and the `Position` of their home and themselves. Also holds their goal
@@ -52,6 +120,7 @@ pub struct FullPlayerInfo {
     // Invariant: Every Player should have their own color
     color: Color,
     goals_reached: u64,
+    goals_history: Vec<Position>,
 }
 
 impl FullPlayerInfo {
@@ -63,6 +132,7 @@ impl FullPlayerInfo {
             goal,
             color,
             goals_reached: 0,
+            goals_history: vec![],
         }
     }
 }
@@ -109,6 +179,27 @@ impl PrivatePlayerInfo for FullPlayerInfo {
     fn inc_goals_reached(&mut self) {
         self.goals_reached += 1;
     }
+
+    fn collect_goal(&mut self, goal: Position) {
+        self.goals_history.push(goal);
+        self.inc_goals_reached();
+    }
+
+    fn goals_history(&self) -> &[Position] {
+        &self.goals_history
+    }
+}
+
+/// Displays as `color@(x,y)→home(x,y) goal(x,y)`
+impl fmt::Display for FullPlayerInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (pos, home, goal) = (self.position, self.home, self.goal);
+        write!(
+            f,
+            "{}@({},{})→home({},{}) goal({},{})",
+            self.color.name, pos.0, pos.1, home.0, home.1, goal.0, goal.1
+        )
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -140,6 +231,18 @@ impl PublicPlayerInfo for PlayerInfo {
     }
 }
 
+/// Displays as `color@(x,y)→home(x,y)`
+impl fmt::Display for PlayerInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (pos, home) = (self.current, self.home);
+        write!(
+            f,
+            "{}@({},{})→home({},{})",
+            self.color.name, pos.0, pos.1, home.0, home.1
+        )
+    }
+}
+
 impl From<FullPlayerInfo> for PlayerInfo {
     fn from(pi: FullPlayerInfo) -> Self {
         PlayerInfo {
@@ -150,6 +253,54 @@ impl From<FullPlayerInfo> for PlayerInfo {
     }
 }
 
+/// This type represents all the data needed to execute a move
+///
+/// # Warning
+/// This type does not self-validate because it has no knowledge of the board it will be played on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlayerMove {
+    pub slide: Slide,
+    pub rotations: usize,
+    pub destination: Position,
+}
+
+/// The effect of a referee applying a `PlayerMove` to a `State` with `State::take_referee_turn`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnEffect {
+    /// The player's move made them win the game.
+    Won,
+    /// The player's move was invalid.
+    Cheated,
+    /// The player's move was valid, but did not make them win the game.
+    Moved,
+}
+
+/// Displays as `slide row 2 East, rotate 90°, move to (1,3)`
+impl fmt::Display for PlayerMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "slide {}, rotate {}°, move to ({},{})",
+            self.slide,
+            self.rotations * 90,
+            self.destination.0,
+            self.destination.1
+        )
+    }
+}
+
+/// The data needed to undo a single move made on a `State`.
+///
+/// Created by `State::snapshot` and consumed by `State::restore`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateSnapshot {
+    /// A hash of the grid of the `Board`, used to tell if a slide was undone correctly
+    grid_hash: u64,
+    spare: Tile,
+    positions: Vec<Position>,
+    previous_slide: Option<Slide>,
+}
+
 /// Represents the State of a single Maze Game.
 #[derive(Debug, PartialEq, Eq)]
 pub struct State<PInfo: PublicPlayerInfo> {
@@ -167,6 +318,12 @@ impl<PInfo: PublicPlayerInfo> State<PInfo> {
         }
     }
 
+    /// Returns the spare `Tile` of the `board`, which is not at any position on the grid
+    #[must_use]
+    pub fn spare_tile(&self) -> &Tile {
+        &self.board.spare
+    }
+
     /// Rotates the spare `Tile` in the `board` by a given number of 90 degree turns
     ///
     /// Does nothing if we do not currently have a spare tile
@@ -185,6 +342,22 @@ impl<PInfo: PublicPlayerInfo> State<PInfo> {
         });
     }
 
+    /// Does `slide` move the same row or column as the previous slide, in the opposite direction?
+    ///
+    /// Such slides undo the previous slide, and are not allowed.
+    pub fn slide_is_undo(&self, slide: Slide) -> bool {
+        self.previous_slide.is_some_and(|prev| {
+            prev.index == slide.index && prev.direction.opposite() == slide.direction
+        })
+    }
+
+    /// Sets the slide the next slide is checked against by `slide_is_undo`, or clears it with
+    /// `None`. This is meant for setting up scenarios, since `slide_and_insert` already keeps track
+    /// of the previous slide.
+    pub fn set_previous_slide(&mut self, slide: Option<Slide>) {
+        self.previous_slide = slide;
+    }
+
     /// Performs a slide and insert action
     ///
     /// # Errors
@@ -210,11 +383,9 @@ impl<PInfo: PublicPlayerInfo> State<PInfo> {
     ///
     /// ```
     pub fn slide_and_insert(&mut self, slide: Slide) -> StateResult<()> {
-        if let Some(prev) = self.previous_slide {
-            if prev.direction.opposite() == slide.direction && prev.index == slide.index {
-                // Kicking player out code can go here
-                Err(StateError::SlideUndo(slide))?;
-            }
+        if self.slide_is_undo(slide) {
+            // Kicking player out code can go here
+            Err(StateError::SlideUndo(slide))?;
         }
         self.board.slide_and_insert(slide)?;
         self.slide_players(&slide);
@@ -242,6 +413,19 @@ impl<PInfo: PublicPlayerInfo> State<PInfo> {
             .expect("Positions in `self.player_info` are never out of bounds")
     }
 
+    /// Returns a Vec of positions reachable by the player at `player_index` in turn order, where
+    /// the active player is at index 0
+    ///
+    /// # Errors
+    /// Errors if there is no player at `player_index`
+    pub fn reachable_for(&self, player_index: usize) -> StateResult<Vec<Position>> {
+        let player = self
+            .player_info
+            .get(player_index)
+            .ok_or(StateError::NoSuchPlayer(player_index))?;
+        Ok(self.board.reachable(player.position())?)
+    }
+
     /// Determines if the currently active `Player` can reach the `Tile` at the given `Position`
     #[must_use]
     pub fn can_reach_position(&self, target: Position) -> bool {
@@ -251,6 +435,25 @@ impl<PInfo: PublicPlayerInfo> State<PInfo> {
             .contains(&target)
     }
 
+    /// Returns every tile on the board that no player can reach from where they are standing. If
+    /// there are no players, every tile is unreachable.
+    pub fn unreachable_tiles(&self) -> HashSet<Position> {
+        let reachable = self
+            .player_info
+            .iter()
+            .flat_map(|pi| {
+                self.board
+                    .reachable(pi.position())
+                    .expect("Player is always on the board")
+            })
+            .collect::<HashSet<_>>();
+        self.board
+            .grid
+            .iter_positions()
+            .filter(|pos| !reachable.contains(pos))
+            .collect()
+    }
+
     /// Checks if the currently active `Player` has landed on its home tile
     #[must_use]
     pub fn player_reached_home(&self) -> bool {
@@ -258,6 +461,44 @@ impl<PInfo: PublicPlayerInfo> State<PInfo> {
         player_info.reached_home()
     }
 
+    /// Returns the colors of every player, in turn order, who is standing on their own home.
+    pub fn players_at_home(&self) -> Vec<Color> {
+        self.player_info
+            .iter()
+            .filter(|pi| pi.reached_home())
+            .map(|pi| pi.color())
+            .collect()
+    }
+
+    /// Returns the colors of every player, in turn order, who is standing on the home of another
+    /// player. Players standing on their own home are never included, even if it is also the home
+    /// of another player.
+    pub fn misplaced_players(&self) -> Vec<Color> {
+        self.player_info
+            .iter()
+            .enumerate()
+            .filter(|(idx, pi)| {
+                !pi.reached_home()
+                    && self
+                        .player_info
+                        .iter()
+                        .enumerate()
+                        .any(|(other, oi)| other != *idx && oi.home() == pi.position())
+            })
+            .map(|(_, pi)| pi.color())
+            .collect()
+    }
+
+    /// Returns the number of players still in the game
+    pub fn player_count(&self) -> usize {
+        self.player_info.len()
+    }
+
+    /// Are there no players left in the game?
+    pub fn is_empty(&self) -> bool {
+        self.player_info.is_empty()
+    }
+
     /// Adds a `Player` to the end of the list of currently active players
     pub fn add_player(&mut self, to_add: PInfo) {
         self.player_info.push_back(to_add);
@@ -265,11 +506,19 @@ impl<PInfo: PublicPlayerInfo> State<PInfo> {
 
     /// Sets `self.active_player` to be the next player by indexing `self.player_info`
     pub fn next_player(&mut self) {
-        if !self.player_info.is_empty() {
+        if !self.is_empty() {
             self.player_info.rotate_left(1);
         }
     }
 
+    /// Advances the turn order by `n` players, as if `next_player` were called `n` times
+    pub fn advance_players(&mut self, n: usize) {
+        if !self.is_empty() {
+            let len = self.player_info.len();
+            self.player_info.rotate_left(n % len);
+        }
+    }
+
     /// Removes the currently active `Player` from game.
     pub fn remove_player(&mut self) -> StateResult<PInfo> {
         self.player_info
@@ -285,6 +534,76 @@ impl<PInfo: PublicPlayerInfo> State<PInfo> {
     pub fn current_player_info_mut(&mut self) -> &mut PInfo {
         &mut self.player_info[0]
     }
+
+    /// Captures the data needed to undo the next move made on this `State`, without cloning the
+    /// `Board`.
+    #[must_use]
+    pub fn snapshot(&self) -> StateSnapshot {
+        StateSnapshot {
+            grid_hash: self.grid_hash(),
+            spare: self.board.spare.clone(),
+            positions: self.player_info.iter().map(|pi| pi.position()).collect(),
+            previous_slide: self.previous_slide,
+        }
+    }
+
+    /// Hashes the grid of the `Board`, without the spare tile.
+    fn grid_hash(&self) -> u64 {
+        let mut hasher = StableHasher::default();
+        self.board.grid.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Undoes the move made since `snap` was taken by sliding the last slid line back and
+    /// restoring the spare tile, player positions and previous slide. If nothing was slid since
+    /// `snap` was taken, only the spare tile, player positions and previous slide are restored.
+    ///
+    /// A move is a slide (with optional spare rotations and player movement) of the active player.
+    /// Nested snapshots must be restored in the reverse order they were taken.
+    ///
+    /// # Errors
+    /// Errors with `StateError::SnapshotMismatch` if more than one slide was made since `snap` was
+    /// taken. In that case, `self` is left unchanged.
+    pub fn restore(&mut self, snap: StateSnapshot) -> StateResult<()> {
+        if self.grid_hash() != snap.grid_hash {
+            let slide = self.previous_slide.ok_or(StateError::SnapshotMismatch)?;
+            let undo = Slide::new_unchecked(slide.index, slide.direction.opposite());
+            self.board
+                .slide_and_insert(undo)
+                .expect("The previous slide was in bounds");
+            if self.grid_hash() != snap.grid_hash {
+                // more than the previous slide was made, so put it back
+                self.board
+                    .slide_and_insert(slide)
+                    .expect("The previous slide was in bounds");
+                return Err(StateError::SnapshotMismatch);
+            }
+        }
+        self.board.spare = snap.spare;
+        self.player_info
+            .iter_mut()
+            .zip(snap.positions)
+            .for_each(|(pi, position)| pi.set_position(position));
+        self.previous_slide = snap.previous_slide;
+        Ok(())
+    }
+}
+
+impl<PInfo: PublicPlayerInfo + PartialEq> State<PInfo> {
+    /// Checks if `self` and `other` are equal, ignoring the order of their players.
+    ///
+    /// Since every player has a unique color, this compares the sets of players, so two states where
+    /// the turn order has been rotated are considered equal.
+    #[must_use]
+    pub fn eq_unordered_players(&self, other: &Self) -> bool {
+        self.board == other.board
+            && self.previous_slide == other.previous_slide
+            && self.player_info.len() == other.player_info.len()
+            && self
+                .player_info
+                .iter()
+                .all(|pi| other.player_info.contains(pi))
+    }
 }
 
 impl<Info: PublicPlayerInfo + Clone> Clone for State<Info> {
@@ -356,10 +675,73 @@ impl<Info: PublicPlayerInfo + Clone> State<Info> {
             .filter(|curr| curr != &start)
             .any(|curr| curr == destination)
     }
+
+    /// Returns every move the active player can legally make this turn.
+    ///
+    /// Moves are ordered by slide (in the order given by `Board::all_slides`), then by rotations of
+    /// the spare tile, then by destination.
+    pub fn legal_moves(&self) -> Vec<PlayerMove> {
+        self.board
+            .all_slides()
+            .into_iter()
+            .cartesian_product(0..4)
+            .flat_map(|(slide, rotations)| {
+                let mut state = self.clone();
+                state.rotate_spare(rotations);
+                if state.slide_and_insert(slide).is_err() {
+                    return vec![];
+                }
+                let start = state.player_info[0].position();
+                let mut destinations = state.reachable_by_player();
+                destinations.retain(|dest| dest != &start);
+                destinations.sort_unstable();
+                destinations
+                    .into_iter()
+                    .map(|destination| PlayerMove {
+                        slide,
+                        rotations,
+                        destination,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
 }
 
 /// Methods for `State<FullPlayerInfo>` types
 impl<Info: PrivatePlayerInfo + Clone> State<Info> {
+    /// Constructs a new `State`, checking that every player's home and goal are on immovable tiles
+    /// and that every player has a unique color. Use `State::new` to skip these checks.
+    ///
+    /// The spare tile has no position on the board, so a home or goal one past the last row or
+    /// column is reported as out of bounds rather than being treated as the spare.
+    ///
+    /// # Errors
+    /// Returns an error if a player's position, home, or goal is out of bounds, if a player's home
+    /// or goal is on a moveable tile, or if two players share a color.
+    pub fn try_new(board: Board, player_info: Vec<Info>) -> StateResult<Self> {
+        if !has_unique_elements(player_info.iter().map(|pi| pi.color())) {
+            return Err(StateError::NonUniqueColors);
+        }
+
+        let immovable = board.possible_homes().collect::<Vec<_>>();
+        for pi in &player_info {
+            for pos in [pi.position(), pi.home(), pi.goal()] {
+                if !board.in_bounds(&pos) {
+                    return Err(board.out_of_bounds(pos).into());
+                }
+            }
+            if !immovable.contains(&pi.home()) {
+                return Err(StateError::HomeMoveableTile(pi.color()));
+            }
+            if !immovable.contains(&pi.goal()) {
+                return Err(StateError::GoalMoveableTile(pi.color()));
+            }
+        }
+
+        Ok(Self::new(board, player_info))
+    }
+
     /// Checks if the currently active `Player` has landed on its goal tile
     #[must_use]
     pub fn player_reached_goal(&self) -> bool {
@@ -367,6 +749,25 @@ impl<Info: PrivatePlayerInfo + Clone> State<Info> {
         player_info.reached_goal()
     }
 
+    /// Checks if the currently active `Player` has won the game with their last move.
+    ///
+    /// A player wins when all of the following are true:
+    /// - they are on their home tile,
+    /// - they are on their goal tile,
+    /// - there are no `remaining_goals` left to hand out,
+    /// - and they have collected at least one goal, so their goal is the home they were sent back
+    ///   to rather than a goal that happened to be assigned on top of their home.
+    ///
+    /// # Panics
+    /// This method panics if `self.player_info` is empty
+    #[must_use]
+    pub fn current_player_has_won(&self, remaining_goals: &VecDeque<Position>) -> bool {
+        self.player_reached_home()
+            && self.player_reached_goal()
+            && remaining_goals.is_empty()
+            && self.current_player_info().get_goals_reached() > 0
+    }
+
     /// Returns `true` if the current player has reached their goal, `false` otherwise
     ///
     /// If the current player has reached their goal:
@@ -388,7 +789,8 @@ impl<Info: PrivatePlayerInfo + Clone> State<Info> {
     /// ```
     pub fn update_current_player_goal(&mut self, remaining_goals: &mut VecDeque<Position>) -> bool {
         if self.player_reached_goal() {
-            self.current_player_info_mut().inc_goals_reached();
+            let goal = self.current_player_info().goal();
+            self.current_player_info_mut().collect_goal(goal);
             if !remaining_goals.is_empty() {
                 // player needs to another goal
                 let goal = remaining_goals
@@ -403,6 +805,178 @@ impl<Info: PrivatePlayerInfo + Clone> State<Info> {
         }
         false
     }
+
+    /// Applies `player_move` for the active player the way a referee does, and returns its
+    /// `TurnEffect`. This does not change the active player.
+    ///
+    /// Winning takes two phases. The player first has to land on their goal, which counts that
+    /// goal as reached and makes the next of `remaining_goals` their goal, or their home if there
+    /// are none left. Only a later move that lands them on their home, after every goal has been
+    /// handed out, wins the game. An invalid move leaves `self` unchanged.
+    ///
+    /// # Panics
+    /// This method panics if `self.player_info` is empty
+    pub fn take_referee_turn(
+        &mut self,
+        PlayerMove {
+            slide,
+            rotations,
+            destination,
+        }: PlayerMove,
+        remaining_goals: &mut VecDeque<Position>,
+    ) -> TurnEffect {
+        if self.try_move(slide, rotations, destination).is_err() {
+            return TurnEffect::Cheated;
+        }
+
+        // This has to happen before the goal is updated, otherwise reaching the goal would
+        // immediately win if it is also the player's home.
+        if self.current_player_has_won(remaining_goals) {
+            return TurnEffect::Won;
+        }
+
+        self.update_current_player_goal(remaining_goals);
+        TurnEffect::Moved
+    }
+
+    /// Returns the sum of the number of goals reached by every player.
+    pub fn total_goals_reached(&self) -> u64 {
+        self.player_info
+            .iter()
+            .map(|pi| pi.get_goals_reached())
+            .sum()
+    }
+
+    /// Returns a hash of the board's connectors, the spare tile, every player in turn order, and the
+    /// previous slide. The hash is made with a `StableHasher`, so it can be pinned in tests to
+    /// catch unintended changes in how games play out.
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = StableHasher::default();
+        hasher.write_terminated(&self.board.connectors_string());
+        hasher.write_terminated(&self.board.spare.connector.to_char().to_string());
+        hasher.write_len(self.player_count());
+        for info in &self.player_info {
+            hasher.write_player_info(info);
+        }
+        hasher.write_terminated(
+            &self
+                .previous_slide
+                .map_or_else(|| "none".to_string(), |slide| slide.to_string()),
+        );
+        hasher.finish()
+    }
+
+    /// Returns the most goals reached by a single player, or 0 if there are no players.
+    pub fn max_goals_reached(&self) -> u64 {
+        self.player_info
+            .iter()
+            .map(|pi| pi.get_goals_reached())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the color, number of goals reached, and squared euclidian distance to their
+    /// current goal of every player, in turn order.
+    ///
+    /// This is the information used to pick the winners of a game that ends without a single
+    /// winner.
+    pub fn score_table(&self) -> Vec<(Color, u64, usize)> {
+        self.player_info
+            .iter()
+            .map(|pi| {
+                (
+                    pi.color(),
+                    pi.get_goals_reached(),
+                    squared_euclidian_distance(&pi.position(), &pi.goal()),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the colors of the players who would win if the game ended in a tie right now, in
+    /// turn order: the players who reached the most goals and, among them, the ones closest to
+    /// their current goal.
+    ///
+    /// This does not change `self`, so it can be used to preview the standings of a game that is
+    /// still going.
+    pub fn preview_winners(&self) -> Vec<Color> {
+        let max_goals = self.max_goals_reached();
+        let table = self
+            .score_table()
+            .into_iter()
+            .filter(|(_, goals, _)| *goals == max_goals)
+            .collect::<Vec<_>>();
+        let min_dist = table.iter().map(|(_, _, dist)| *dist).min();
+        table
+            .into_iter()
+            .filter(|(_, _, dist)| Some(*dist) == min_dist)
+            .map(|(color, _, _)| color)
+            .collect()
+    }
+
+    /// Returns every legal move that lands the active player on their goal this turn.
+    pub fn winning_moves(&self) -> Vec<PlayerMove> {
+        let goal = self.current_player_info().goal();
+        self.legal_moves()
+            .into_iter()
+            .filter(|PlayerMove { destination, .. }| *destination == goal)
+            .collect()
+    }
+
+    /// Checks if the active player can win the game this turn, given the goals that have not
+    /// been handed out yet.
+    ///
+    /// The player can only win by reaching their goal if that goal is their home and they have
+    /// already collected a goal, as in `State::current_player_has_won`, and one of
+    /// `State::winning_moves` gets them there.
+    #[must_use]
+    pub fn active_player_can_win(&self, remaining_goals: &VecDeque<Position>) -> bool {
+        let info = self.current_player_info();
+        remaining_goals.is_empty()
+            && info.goal() == info.home()
+            && info.get_goals_reached() > 0
+            && !self.winning_moves().is_empty()
+    }
+
+    /// Finds the goal in `goals` closest to the active player that they can reach this turn,
+    /// after some legal slide and rotation of the spare tile.
+    ///
+    /// Distance is measured with `squared_euclidian_distance` from where the active player is
+    /// standing before the slide, and ties go to the goal that comes first in `goals`. Returns
+    /// `None` if there are no players or none of `goals` can be reached this turn.
+    #[must_use]
+    pub fn nearest_reachable_goal_for_active(&self, goals: &[Position]) -> Option<Position> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let start = self.current_player_info().position();
+        let reachable: HashSet<Position> = self
+            .board
+            .all_slides()
+            .into_iter()
+            .cartesian_product(0..4)
+            .filter_map(|(slide, rotations)| {
+                let mut state = self.clone();
+                state.rotate_spare(rotations);
+                state.slide_and_insert(slide).ok()?;
+                let moved_to = state.player_info[0].position();
+                Some(
+                    state
+                        .reachable_by_player()
+                        .into_iter()
+                        .filter(move |pos| *pos != moved_to),
+                )
+            })
+            .flatten()
+            .collect();
+
+        goals
+            .iter()
+            .copied()
+            .filter(|goal| reachable.contains(goal))
+            .min_by_key(|goal| squared_euclidian_distance(&start, goal))
+    }
 }
 
 impl<PInfo: PublicPlayerInfo + Clone> Default for State<PInfo> {
@@ -429,10 +1003,116 @@ impl From<State<FullPlayerInfo>> for State<PlayerInfo> {
     }
 }
 
+impl State<FullPlayerInfo> {
+    /// Draws this `State` as plain text, for pasting into terminals and issues.
+    ///
+    /// Every row of the board is one line, and every cell is three characters followed by a space:
+    /// - the connector of the tile, as in `Board::connectors_string`
+    /// - the uppercase first letter of the color of the player standing on the tile
+    /// - the lowercase first letter of the color of the player whose home is the tile
+    ///
+    /// Either letter is `*` if more than one player stands on or lives at that tile, and a space if
+    /// none does. Trailing spaces are trimmed. After a blank line comes the spare tile, then one
+    /// legend line per player in turn order.
+    ///
+    /// ```text
+    /// ─   │   └
+    /// ┌   ┐ r ┘R
+    /// ┴Bb ├   ┬
+    ///
+    /// spare: ┼
+    /// R: red@(2,1)→home(1,1) goal(0,0)
+    /// B: blue@(0,2)→home(0,2) goal(2,2)
+    /// ```
+    pub fn render_ascii(&self) -> String {
+        let letter_at = |pos: Position, of: fn(&FullPlayerInfo) -> Position| {
+            let mut colors = self
+                .player_info
+                .iter()
+                .filter(|pi| of(pi) == pos)
+                .map(|pi| pi.color.name.chars().next().unwrap_or(' '));
+            match (colors.next(), colors.next()) {
+                (None, _) => ' ',
+                (Some(letter), None) => letter,
+                (Some(_), Some(_)) => '*',
+            }
+        };
+
+        let mut lines = self
+            .board
+            .grid
+            .iter()
+            .enumerate()
+            .map(|(row_idx, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(col_idx, tile)| {
+                        let pos = (col_idx, row_idx);
+                        format!(
+                            "{}{}{}",
+                            tile.connector.to_char(),
+                            letter_at(pos, FullPlayerInfo::position).to_ascii_uppercase(),
+                            letter_at(pos, FullPlayerInfo::home).to_ascii_lowercase(),
+                        )
+                    })
+                    .join(" ")
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+
+        lines.push(String::new());
+        lines.push(format!("spare: {}", self.board.spare.connector.to_char()));
+        for pi in &self.player_info {
+            let letter = pi.color.name.chars().next().unwrap_or(' ');
+            lines.push(format!("{}: {pi}", letter.to_ascii_uppercase()));
+        }
+        lines.join("\n")
+    }
+}
+
+/// The differences between two `State`s, as reported by `diff_states`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StateDiff {
+    /// The color, old position, and new position of every player that moved
+    pub moved: Vec<(Color, Position, Position)>,
+    /// The color, old goal, and new goal of every player whose goal changed
+    pub goals_changed: Vec<(Color, Position, Position)>,
+    /// The slide that was applied between the two `State`s, if any
+    pub slide: Option<Slide>,
+}
+
+/// Compares two consecutive frames of a game, reporting which players moved, whose goals changed,
+/// and the slide that was applied in between.
+///
+/// Players are matched up by their color, so players that are only in one of the `State`s are
+/// ignored.
+pub fn diff_states(before: &State<FullPlayerInfo>, after: &State<FullPlayerInfo>) -> StateDiff {
+    let mut diff = StateDiff::default();
+    for new in &after.player_info {
+        if let Some(old) = before.player_info.iter().find(|old| old.color == new.color) {
+            if old.position != new.position {
+                diff.moved.push((new.color(), old.position, new.position));
+            }
+            if old.goal != new.goal {
+                diff.goals_changed.push((new.color(), old.goal, new.goal));
+            }
+        }
+    }
+
+    if after.previous_slide != before.previous_slide || after.board != before.board {
+        diff.slide = after.previous_slide;
+    }
+    diff
+}
+
 #[cfg(test)]
 mod state_tests {
     use crate::{
+        board::OutOfBounds,
         color::ColorName,
+        gem::Gem,
+        grid::Grid,
         tile::{
             CompassDirection::{self, *},
             ConnectorShape::*,
@@ -454,6 +1134,7 @@ mod state_tests {
             goal: (1, 1),
             color: ColorName::Red.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
 
         assert!(!state.player_info.is_empty());
@@ -470,6 +1151,154 @@ mod state_tests {
         assert_eq!(state.player_info.len(), 2);
     }
 
+    #[test]
+    fn test_player_count() {
+        let mut state: State<FullPlayerInfo> = State::default();
+        assert!(state.is_empty());
+        assert_eq!(state.player_count(), 0);
+        assert_eq!(state.player_count(), state.player_info.len());
+
+        state.add_player(FullPlayerInfo::new(
+            (1, 1),
+            (1, 1),
+            (3, 3),
+            ColorName::Red.into(),
+        ));
+        assert!(!state.is_empty());
+        assert_eq!(state.player_count(), 1);
+        assert_eq!(state.player_count(), state.player_info.len());
+
+        for (home, color) in [((3, 3), ColorName::Blue), ((5, 5), ColorName::Green)] {
+            state.add_player(FullPlayerInfo::new(home, home, (1, 1), color.into()));
+        }
+        assert!(!state.is_empty());
+        assert_eq!(state.player_count(), 3);
+        assert_eq!(state.player_count(), state.player_info.len());
+
+        state.remove_player().unwrap();
+        assert_eq!(state.player_count(), 2);
+    }
+
+    #[test]
+    fn test_render_ascii() {
+        let mut state: State<FullPlayerInfo> = State::new(
+            board::DefaultBoard::<3, 3>::default_board(),
+            vec![
+                FullPlayerInfo::new((1, 1), (2, 1), (0, 0), ColorName::Red.into()),
+                FullPlayerInfo::new((0, 2), (0, 2), (2, 2), ColorName::Blue.into()),
+            ],
+        );
+        assert_eq!(
+            state.render_ascii(),
+            [
+                "─   │   └",
+                "┌   ┐ r ┘R",
+                "┴Bb ├   ┬",
+                "",
+                "spare: ┼",
+                "R: red@(2,1)→home(1,1) goal(0,0)",
+                "B: blue@(0,2)→home(0,2) goal(2,2)",
+            ]
+            .join("\n")
+        );
+
+        state.player_info[0].set_position((0, 2));
+        let rendered = state.render_ascii();
+        assert_eq!(rendered.lines().nth(2), Some("┴*b ├   ┬"));
+        assert_eq!(rendered.lines().nth(1), Some("┌   ┐ r ┘"));
+    }
+
+    #[test]
+    fn test_unreachable_tiles() {
+        let board =
+            Board::from_connectors_str("┼┼┼\n┼┼┼\n┼┼┌", || Gem::pair_from_num(0)).unwrap();
+        let mut state: State<FullPlayerInfo> = State::new(board, vec![]);
+        assert_eq!(state.unreachable_tiles().len(), 9);
+
+        state.add_player(FullPlayerInfo::new(
+            (1, 1),
+            (0, 0),
+            (1, 1),
+            ColorName::Red.into(),
+        ));
+        assert_eq!(state.unreachable_tiles(), HashSet::from([(2, 2)]));
+
+        // a player standing on the isolated tile can reach it
+        state.add_player(FullPlayerInfo::new(
+            (1, 1),
+            (2, 2),
+            (1, 1),
+            ColorName::Blue.into(),
+        ));
+        assert!(state.unreachable_tiles().is_empty());
+
+        let board = Board::from_connectors_str(&["┼┼┼┼┼┼┼"; 7].join("\n"), || {
+            Gem::pair_from_num(0)
+        })
+        .unwrap();
+        let state: State<FullPlayerInfo> = State::new(
+            board,
+            vec![FullPlayerInfo::new(
+                (1, 1),
+                (1, 1),
+                (5, 5),
+                ColorName::Red.into(),
+            )],
+        );
+        assert!(state.unreachable_tiles().is_empty());
+    }
+
+    #[test]
+    fn test_players_at_home() {
+        let mut state: State<FullPlayerInfo> = State::default();
+        assert!(state.players_at_home().is_empty());
+
+        let infos = [
+            ((1, 1), (1, 1), ColorName::Red),
+            ((3, 3), (1, 1), ColorName::Blue),
+            ((5, 5), (5, 5), ColorName::Green),
+        ];
+        for (home, position, color) in infos {
+            state.add_player(FullPlayerInfo::new(home, position, (1, 5), color.into()));
+        }
+        assert_eq!(
+            state.players_at_home(),
+            vec![Color::from(ColorName::Red), Color::from(ColorName::Green)]
+        );
+
+        state.next_player();
+        assert_eq!(
+            state.players_at_home(),
+            vec![Color::from(ColorName::Green), Color::from(ColorName::Red)]
+        );
+    }
+
+    #[test]
+    fn test_misplaced_players() {
+        let mut state: State<FullPlayerInfo> = State::default();
+        assert!(state.misplaced_players().is_empty());
+
+        let infos = [
+            ((1, 1), (3, 3), ColorName::Red),
+            ((3, 3), (3, 3), ColorName::Blue),
+            ((5, 5), (0, 0), ColorName::Green),
+        ];
+        for (home, position, color) in infos {
+            state.add_player(FullPlayerInfo::new(home, position, (1, 5), color.into()));
+        }
+        assert_eq!(state.misplaced_players(), vec![Color::from(ColorName::Red)]);
+
+        state.player_info[0].set_position((1, 1));
+        assert!(state.misplaced_players().is_empty());
+
+        state.player_info[2].set_position((1, 1));
+        state.player_info[1].set_position((5, 5));
+        assert_eq!(
+            state.misplaced_players(),
+            vec![Color::from(ColorName::Blue), Color::from(ColorName::Green)]
+        );
+    }
+
     #[test]
     fn test_remove_player() {
         let mut state = State::default();
@@ -520,6 +1349,31 @@ mod state_tests {
         assert_eq!(state.player_info[0], p1);
     }
 
+    #[test]
+    fn test_advance_players() {
+        let mut state = State::<FullPlayerInfo>::default();
+        // Does not fail
+        state.advance_players(2);
+
+        let p1 = FullPlayerInfo::new((0, 0), (0, 0), (1, 1), ColorName::Red.into());
+        let p2 = FullPlayerInfo::new((0, 0), (0, 0), (1, 3), ColorName::Green.into());
+        let p3 = FullPlayerInfo::new((0, 0), (0, 0), (1, 5), ColorName::Yellow.into());
+        state.add_player(p1.clone());
+        state.add_player(p2.clone());
+        state.add_player(p3.clone());
+
+        state.advance_players(3);
+        assert_eq!(state.player_info, vec![p1.clone(), p2.clone(), p3.clone()]);
+
+        state.advance_players(2);
+        assert_eq!(state.player_info, vec![p3.clone(), p1.clone(), p2.clone()]);
+
+        state.advance_players(0);
+        assert_eq!(state.player_info[0], p3);
+        state.advance_players(7);
+        assert_eq!(state.player_info[0], p1);
+    }
+
     #[test]
     fn test_slide_and_insert() {
         let mut state: State<FullPlayerInfo> = State::default();
@@ -542,13 +1396,42 @@ mod state_tests {
         let res = state.slide_and_insert(state.board.new_slide(0, South).unwrap());
         assert!(res.is_err());
 
-        // Doing it twice should not matter
-        let res = state.slide_and_insert(state.board.new_slide(0, South).unwrap());
-        assert!(res.is_err());
+        // Doing it twice should not matter
+        let res = state.slide_and_insert(state.board.new_slide(0, South).unwrap());
+        assert!(res.is_err());
+
+        // Doing it in another index is fine
+        let res = state.slide_and_insert(state.board.new_slide(2, South).unwrap());
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_slide_is_undo() {
+        let mut state: State<FullPlayerInfo> = State::default();
+        let undo = state.board.new_slide(0, South).unwrap();
+        assert!(!state.slide_is_undo(undo));
+
+        state
+            .slide_and_insert(state.board.new_slide(0, North).unwrap())
+            .unwrap();
+        assert!(state.slide_is_undo(undo));
+        assert!(!state.slide_is_undo(state.board.new_slide(0, North).unwrap()));
+        assert!(!state.slide_is_undo(state.board.new_slide(2, South).unwrap()));
+        assert!(!state.slide_is_undo(state.board.new_slide(0, West).unwrap()));
+    }
+
+    #[test]
+    fn test_set_previous_slide() {
+        let mut state: State<FullPlayerInfo> = State::default();
+        let undo = state.board.new_slide(0, South).unwrap();
+
+        state.set_previous_slide(state.board.new_slide(0, North));
+        assert!(state.slide_is_undo(undo));
+        assert!(state.slide_and_insert(undo).is_err());
 
-        // Doing it in another index is fine
-        let res = state.slide_and_insert(state.board.new_slide(2, South).unwrap());
-        assert!(res.is_ok());
+        state.set_previous_slide(None);
+        assert!(!state.slide_is_undo(undo));
+        assert!(state.slide_and_insert(undo).is_ok());
     }
 
     #[test]
@@ -635,6 +1518,7 @@ mod state_tests {
             goal: (1, 1),
             color: ColorName::Yellow.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         state.player_info.push_back(FullPlayerInfo {
             home: (3, 1),
@@ -642,6 +1526,7 @@ mod state_tests {
             goal: (1, 1),
             color: ColorName::Red.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
 
         // Default Board<7> is:
@@ -710,6 +1595,7 @@ mod state_tests {
             goal: (1, 1),
             color: ColorName::Yellow.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         state.player_info.push_back(FullPlayerInfo {
             home: (3, 1),
@@ -717,6 +1603,7 @@ mod state_tests {
             goal: (1, 1),
             color: ColorName::Red.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         // Default Board<7> is:
         //   0123456
@@ -734,7 +1621,7 @@ mod state_tests {
         assert!(!state.is_valid_move(Slide::new_unchecked(0, CompassDirection::South), 1, (1, 1)));
         assert!(!state.is_valid_move(Slide::new_unchecked(1, CompassDirection::North), 2, (2, 1)));
 
-        state.previous_slide = state.board.new_slide(0, CompassDirection::East);
+        state.set_previous_slide(state.board.new_slide(0, CompassDirection::East));
 
         assert!(!state.is_valid_move(Slide::new_unchecked(0, CompassDirection::West), 0, (2, 1)));
     }
@@ -748,6 +1635,7 @@ mod state_tests {
             goal: (1, 1),
             color: ColorName::Yellow.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         state.player_info.push_back(FullPlayerInfo {
             home: (3, 1),
@@ -755,6 +1643,7 @@ mod state_tests {
             goal: (1, 1),
             color: ColorName::Red.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         // Default Board<7> is:
         //   0123456
@@ -829,6 +1718,7 @@ mod state_tests {
             goal: (1, 1),
             color: ColorName::Yellow.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         state.player_info.push_back(FullPlayerInfo {
             home: (3, 1),
@@ -836,6 +1726,7 @@ mod state_tests {
             goal: (1, 1),
             color: ColorName::Red.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         state.player_info.push_back(FullPlayerInfo {
             home: (3, 1),
@@ -843,6 +1734,7 @@ mod state_tests {
             goal: (3, 3),
             color: ColorName::Green.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         // Default Board<7> is:
         //   0123456
@@ -888,6 +1780,32 @@ mod state_tests {
 
     // #[test]
     // #[should_panic]
+    #[test]
+    fn test_goals_history() {
+        let mut state: State<FullPlayerInfo> = State::default();
+        state.add_player(FullPlayerInfo::new(
+            (5, 5),
+            (1, 1),
+            (1, 1),
+            ColorName::Red.into(),
+        ));
+        let mut remaining_goals = VecDeque::from([(3, 3)]);
+        assert!(state.current_player_info().goals_history().is_empty());
+
+        assert!(state.update_current_player_goal(&mut remaining_goals));
+        assert_eq!(state.current_player_info().goals_history(), &[(1, 1)]);
+
+        state.current_player_info_mut().set_position((3, 3));
+        assert!(state.update_current_player_goal(&mut remaining_goals));
+        let player = state.current_player_info();
+        assert_eq!(player.goals_history(), &[(1, 1), (3, 3)]);
+        assert_eq!(
+            player.get_goals_reached(),
+            player.goals_history().len() as u64
+        );
+        assert_eq!(player.goal(), (5, 5));
+    }
+
     // fn test_update_current_player_goal_empty() {
     //     let mut state: State<FullPlayerInfo> = State::default();
     //     state.update_current_player_goal(&mut VecDeque::new());
@@ -902,6 +1820,7 @@ mod state_tests {
             goal: (1, 1),
             color: ColorName::Green.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         state.player_info.push_back(FullPlayerInfo {
             home: (3, 1),
@@ -909,6 +1828,7 @@ mod state_tests {
             goal: (1, 1),
             color: ColorName::Red.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         state.player_info.push_back(FullPlayerInfo {
             home: (5, 1),
@@ -916,6 +1836,7 @@ mod state_tests {
             goal: (1, 1),
             color: ColorName::Purple.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         // Default Board<7> is:
         //   0123456
@@ -1037,6 +1958,7 @@ mod state_tests {
             goal: (1, 1),
             color: ColorName::Yellow.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         state.player_info.push_back(FullPlayerInfo {
             home: (3, 1),
@@ -1044,6 +1966,7 @@ mod state_tests {
             goal: (1, 3),
             color: ColorName::Red.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         state.player_info.push_back(FullPlayerInfo {
             home: (5, 1),
@@ -1051,6 +1974,7 @@ mod state_tests {
             goal: (1, 5),
             color: ColorName::Blue.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
 
         // Default Board<7> is:
@@ -1102,6 +2026,7 @@ mod state_tests {
             goal: (1, 1),
             color: ColorName::Blue.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         assert!(!state.player_reached_home());
 
@@ -1113,6 +2038,7 @@ mod state_tests {
             goal: (1, 3),
             color: ColorName::Red.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         state.next_player();
         assert!(!state.player_reached_home());
@@ -1126,6 +2052,7 @@ mod state_tests {
             goal: (1, 1),
             color: ColorName::Green.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         state.player_info.push_front(FullPlayerInfo {
             home: (3, 1),
@@ -1133,6 +2060,7 @@ mod state_tests {
             goal: (1, 3),
             color: ColorName::Blue.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         assert!(state.player_reached_home());
         state.next_player();
@@ -1149,6 +2077,7 @@ mod state_tests {
             goal: (1, 3),
             color: ColorName::Red.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         assert!(!state.player_reached_goal());
 
@@ -1159,8 +2088,594 @@ mod state_tests {
             goal: (2, 3),
             color: ColorName::Green.into(),
             goals_reached: 0,
+            goals_history: vec![],
         });
         state.next_player();
         assert!(state.player_reached_goal());
     }
+
+    #[test]
+    fn test_current_player_has_won() {
+        let player = |position, goals_reached| FullPlayerInfo {
+            home: (1, 1),
+            position,
+            goal: (1, 1),
+            color: ColorName::Red.into(),
+            goals_reached,
+            goals_history: vec![],
+        };
+
+        // at home, but no goals have been collected
+        let mut state = State::default();
+        state.player_info.push_back(player((1, 1), 0));
+        assert!(!state.current_player_has_won(&VecDeque::new()));
+
+        // collected a goal and made it home
+        let mut state = State::default();
+        state.player_info.push_back(player((1, 1), 1));
+        assert!(state.current_player_has_won(&VecDeque::new()));
+
+        // there are still goals left to collect
+        assert!(!state.current_player_has_won(&vec![(3, 3)].into()));
+
+        // collected a goal but not yet home
+        let mut state = State::default();
+        state.player_info.push_back(player((1, 3), 1));
+        assert!(!state.current_player_has_won(&VecDeque::new()));
+    }
+
+    #[test]
+    fn test_diff_states() {
+        let mut before = State::default();
+        for (home, goal, color) in [
+            ((1, 1), (5, 5), ColorName::Red),
+            ((3, 3), (1, 5), ColorName::Blue),
+        ] {
+            before.add_player(FullPlayerInfo::new(home, home, goal, color.into()));
+        }
+        assert_eq!(diff_states(&before, &before), StateDiff::default());
+
+        // row 2 has no players on it, so only the red player moves
+        let slide = Slide::new_unchecked(2, East);
+        let mut after = before.clone();
+        after.slide_and_insert(slide).unwrap();
+        after.current_player_info_mut().set_position((1, 3));
+
+        assert_eq!(
+            diff_states(&before, &after),
+            StateDiff {
+                moved: vec![(ColorName::Red.into(), (1, 1), (1, 3))],
+                goals_changed: vec![],
+                slide: Some(slide),
+            }
+        );
+    }
+
+    #[test]
+    fn test_try_new() {
+        let red = FullPlayerInfo::new((1, 1), (0, 0), (5, 5), ColorName::Red.into());
+        let blue = FullPlayerInfo::new((3, 3), (3, 3), (1, 5), ColorName::Blue.into());
+        let state = State::try_new(Board::default(), vec![red.clone(), blue.clone()]).unwrap();
+        assert_eq!(state.player_info, vec![red.clone(), blue.clone()]);
+
+        // column 0 slides, so (0, 1) is a moveable tile
+        let moveable_home = FullPlayerInfo::new((0, 1), (0, 1), (5, 5), ColorName::Green.into());
+        assert!(matches!(
+            State::try_new(Board::default(), vec![red.clone(), moveable_home]),
+            Err(StateError::HomeMoveableTile(color)) if color == ColorName::Green.into()
+        ));
+
+        let moveable_goal = FullPlayerInfo::new((5, 1), (5, 1), (4, 4), ColorName::Green.into());
+        assert!(matches!(
+            State::try_new(Board::default(), vec![moveable_goal]),
+            Err(StateError::GoalMoveableTile(_))
+        ));
+
+        let off_board = FullPlayerInfo::new((5, 1), (7, 1), (5, 5), ColorName::Green.into());
+        assert!(matches!(
+            State::try_new(Board::default(), vec![off_board]),
+            Err(StateError::BoardError(_))
+        ));
+
+        let also_red = FullPlayerInfo::new((5, 1), (5, 1), (3, 3), ColorName::Red.into());
+        assert!(matches!(
+            State::try_new(Board::default(), vec![red, also_red]),
+            Err(StateError::NonUniqueColors)
+        ));
+    }
+
+    #[test]
+    fn test_spare_tile_boundary() {
+        let state = State::<FullPlayerInfo>::default();
+        assert_eq!(state.spare_tile(), &state.board.spare);
+
+        // one past the last column
+        let home_past_col = FullPlayerInfo::new((1, 1), (7, 1), (5, 5), ColorName::Red.into());
+        assert!(matches!(
+            State::try_new(Board::default(), vec![home_past_col]),
+            Err(StateError::BoardError(OutOfBounds::Position((7, 1), ..)))
+        ));
+
+        // one past the last row
+        let goal_past_row = FullPlayerInfo::new((1, 1), (1, 1), (1, 7), ColorName::Red.into());
+        assert!(matches!(
+            State::try_new(Board::default(), vec![goal_past_row]),
+            Err(StateError::BoardError(OutOfBounds::Position((1, 7), ..)))
+        ));
+    }
+
+    #[test]
+    fn test_goals_reached_totals() {
+        let mut state = State::default();
+        assert_eq!(state.total_goals_reached(), 0);
+        assert_eq!(state.max_goals_reached(), 0);
+
+        let mut red = FullPlayerInfo::new((1, 1), (1, 1), (5, 5), ColorName::Red.into());
+        (0..2).for_each(|_| red.inc_goals_reached());
+        let mut blue = FullPlayerInfo::new((3, 3), (3, 3), (5, 1), ColorName::Blue.into());
+        (0..3).for_each(|_| blue.inc_goals_reached());
+        state.add_player(red);
+        state.add_player(blue);
+        assert_eq!(state.total_goals_reached(), 5);
+        assert_eq!(state.max_goals_reached(), 3);
+    }
+
+    #[test]
+    fn test_state_hash() {
+        let mut state: State<FullPlayerInfo> = State::default();
+        state.add_player(FullPlayerInfo::new(
+            (1, 1),
+            (1, 1),
+            (5, 5),
+            ColorName::Red.into(),
+        ));
+        assert_eq!(state.state_hash(), state.clone().state_hash());
+
+        let before = state.state_hash();
+        state
+            .slide_and_insert(Slide::new_unchecked(0, East))
+            .unwrap();
+        let after_slide = state.state_hash();
+        assert_ne!(before, after_slide);
+
+        state.current_player_info_mut().inc_goals_reached();
+        assert_ne!(after_slide, state.state_hash());
+    }
+
+    #[test]
+    fn test_score_table() {
+        let mut state = State::default();
+        for (home, position, goal, color) in [
+            ((1, 1), (0, 0), (1, 5), ColorName::Red),
+            ((1, 5), (1, 0), (1, 1), ColorName::Blue),
+            ((3, 3), (3, 3), (5, 5), ColorName::Green),
+        ] {
+            state.add_player(FullPlayerInfo::new(home, position, goal, color.into()));
+        }
+        state.player_info[2].inc_goals_reached();
+        state.player_info[2].inc_goals_reached();
+
+        assert_eq!(
+            state.score_table(),
+            vec![
+                (ColorName::Red.into(), 0, 26),
+                (ColorName::Blue.into(), 0, 1),
+                (ColorName::Green.into(), 2, 8),
+            ]
+        );
+        assert!(State::<FullPlayerInfo>::default().score_table().is_empty());
+    }
+
+    #[test]
+    fn test_legal_moves() {
+        let mut state = State::default();
+        state.player_info.push_back(FullPlayerInfo {
+            home: (1, 1),
+            position: (1, 1),
+            goal: (5, 5),
+            color: ColorName::Red.into(),
+            goals_reached: 0,
+            goals_history: vec![],
+        });
+
+        let moves = state.legal_moves();
+        assert!(!moves.is_empty());
+        assert!(moves.iter().all(
+            |&PlayerMove {
+                 slide,
+                 rotations,
+                 destination,
+             }| state.is_valid_move(slide, rotations, destination)
+        ));
+
+        // moves that undo the previous slide are not legal
+        state.set_previous_slide(state.board.new_slide(0, North));
+        let undo = state.board.new_slide(0, South).unwrap();
+        assert!(state.legal_moves().iter().all(|mv| mv.slide != undo));
+    }
+
+    #[test]
+    fn test_winning_moves() {
+        let mut state = State::default();
+        state.player_info.push_back(FullPlayerInfo {
+            home: (1, 1),
+            position: (3, 1),
+            goal: (4, 1),
+            color: ColorName::Red.into(),
+            goals_reached: 0,
+            goals_history: vec![],
+        });
+        // Default Board<7> is:
+        //   0123456
+        // 0 ─│└┌┐┘┴
+        // 1 ├┬┤┼─│└
+        // 2 ┌┐┘┴├┬┤
+        // 3 ┼─│└┌┐┘
+        // 4 ┴├┬┤┼─│
+        // 5 └┌┐┘┴├┬
+        // 6 ┤┼─│└┌┐
+        //
+        // extra = ┼
+
+        // sliding column 0 or row 0 leaves the path from (3, 1) to (4, 1) untouched
+        let moves = state.winning_moves();
+        assert!(moves.iter().all(|mv| mv.destination == (4, 1)));
+        assert!(moves.contains(&PlayerMove {
+            slide: state.board.new_slide(0, North).unwrap(),
+            rotations: 0,
+            destination: (4, 1),
+        }));
+        assert!(moves.contains(&PlayerMove {
+            slide: state.board.new_slide(0, East).unwrap(),
+            rotations: 0,
+            destination: (4, 1),
+        }));
+
+        // on a board of horizontal paths, the player can never leave row 1
+        state.board.grid = Grid::from([[(); 7]; 7].map(|list| {
+            list.map(|_| Tile {
+                connector: Path(Horizontal),
+                gems: (Gem::Zircon, Gem::Zoisite).into(),
+            })
+        }));
+        state.board.spare.connector = Path(Horizontal);
+        state.player_info[0].position = (1, 1);
+        state.player_info[0].goal = (1, 5);
+        assert!(state.winning_moves().is_empty());
+    }
+
+    #[test]
+    fn test_active_player_can_win() {
+        let mut state = State::default();
+        state.player_info.push_back(FullPlayerInfo {
+            home: (4, 1),
+            position: (3, 1),
+            goal: (4, 1),
+            color: ColorName::Red.into(),
+            goals_reached: 1,
+            goals_history: vec![],
+        });
+        let no_goals = VecDeque::new();
+
+        // as in `test_winning_moves`, (4, 1) can be reached from (3, 1)
+        assert!(!state.winning_moves().is_empty());
+        assert!(state.active_player_can_win(&no_goals));
+        assert!(!state.active_player_can_win(&VecDeque::from([(1, 1)])));
+
+        // reaching a goal that is not the player's home does not win
+        state.player_info[0].home = (1, 1);
+        assert!(!state.active_player_can_win(&no_goals));
+
+        // neither does reaching a home that was assigned as the first goal
+        state.player_info[0].home = (4, 1);
+        state.player_info[0].goals_reached = 0;
+        assert!(!state.active_player_can_win(&no_goals));
+
+        // on a board of horizontal paths, the player can never leave row 1
+        state.player_info[0].goals_reached = 1;
+        state.board.grid = Grid::from([[(); 7]; 7].map(|list| {
+            list.map(|_| Tile {
+                connector: Path(Horizontal),
+                gems: (Gem::Zircon, Gem::Zoisite).into(),
+            })
+        }));
+        state.board.spare.connector = Path(Horizontal);
+        state.player_info[0].position = (1, 1);
+        state.player_info[0].home = (1, 5);
+        state.player_info[0].goal = (1, 5);
+        assert!(state.winning_moves().is_empty());
+        assert!(!state.active_player_can_win(&no_goals));
+    }
+
+    #[test]
+    fn test_nearest_reachable_goal_for_active() {
+        let mut state = State::default();
+        assert_eq!(state.nearest_reachable_goal_for_active(&[(1, 1)]), None);
+
+        state.player_info.push_back(FullPlayerInfo::new(
+            (1, 1),
+            (0, 0),
+            (1, 1),
+            ColorName::Red.into(),
+        ));
+
+        // from (0, 0), no slide lets the player reach (3, 3)
+        assert_eq!(state.nearest_reachable_goal_for_active(&[(3, 3)]), None);
+        assert_eq!(
+            state.nearest_reachable_goal_for_active(&[(3, 3), (1, 5)]),
+            Some((1, 5))
+        );
+
+        // both are reachable, but (1, 1) is closer
+        assert_eq!(
+            state.nearest_reachable_goal_for_active(&[(1, 5), (1, 1)]),
+            Some((1, 1))
+        );
+        assert_eq!(state.nearest_reachable_goal_for_active(&[]), None);
+    }
+
+    #[test]
+    fn test_take_referee_turn() {
+        // mirrors `test_process_move` in the referee, on the default board:
+        //   0123456
+        // 0 ─│└┌┐┘┴
+        // 1 ├┬┤┼─│└
+        // 2 ┌┐┘┴├┬┤
+        // 3 ┼─│└┌┐┘
+        // 4 ┴├┬┤┼─│
+        // 5 └┌┐┘┴├┬
+        // 6 ┤┼─│└┌┐
+        //
+        // extra = ┼
+        let mut green = FullPlayerInfo::new((5, 3), (4, 3), (5, 3), ColorName::Green.into());
+        green.goals_reached = 1;
+        let mut state: State<FullPlayerInfo> = State {
+            player_info: VecDeque::from([
+                FullPlayerInfo::new((1, 3), (1, 1), (5, 3), ColorName::Red.into()),
+                FullPlayerInfo::new((1, 3), (1, 1), (3, 3), ColorName::Blue.into()),
+                FullPlayerInfo::new((1, 5), (1, 4), (3, 5), ColorName::Yellow.into()),
+                green,
+            ]),
+            previous_slide: Some(Slide::new_unchecked(0, East)),
+            ..Default::default()
+        };
+
+        // Red undoes the previous slide
+        let red_move = PlayerMove {
+            slide: Slide::new_unchecked(0, West),
+            rotations: 0,
+            destination: (2, 1),
+        };
+        let before = state.clone();
+        let effect = state.take_referee_turn(red_move, &mut VecDeque::new());
+        assert_eq!(effect, TurnEffect::Cheated);
+        assert_eq!(state, before);
+
+        state.next_player();
+
+        // Blue moves without reaching their goal
+        let blue_move = PlayerMove {
+            slide: Slide::new_unchecked(0, North),
+            rotations: 0,
+            destination: (0, 3),
+        };
+        let effect = state.take_referee_turn(blue_move, &mut VecDeque::new());
+        assert_eq!(effect, TurnEffect::Moved);
+        assert_eq!(state.current_player_info().color, ColorName::Blue.into());
+        assert_eq!(state.current_player_info().position(), (0, 3));
+        assert_eq!(state.current_player_info().goal(), (3, 3));
+
+        state.next_player();
+
+        // Yellow reaches their goal and is given the next one
+        let yellow_move = PlayerMove {
+            slide: Slide::new_unchecked(0, North),
+            rotations: 0,
+            destination: (3, 5),
+        };
+        let mut remaining = VecDeque::from([(1, 1)]);
+        let effect = state.take_referee_turn(yellow_move, &mut remaining);
+        assert_eq!(effect, TurnEffect::Moved);
+        assert_eq!(state.current_player_info().color, ColorName::Yellow.into());
+        assert_eq!(state.current_player_info().position(), (3, 5));
+        assert_eq!(state.current_player_info().goal(), (1, 1));
+        assert_eq!(state.current_player_info().get_goals_reached(), 1);
+        assert!(remaining.is_empty());
+
+        state.next_player();
+
+        // Green has reached a goal, and wins by going home
+        let green_move = PlayerMove {
+            slide: Slide::new_unchecked(0, East),
+            rotations: 0,
+            destination: (5, 3),
+        };
+        let effect = state.take_referee_turn(green_move, &mut VecDeque::new());
+        assert_eq!(effect, TurnEffect::Won);
+        assert_eq!(state.current_player_info().color, ColorName::Green.into());
+        assert_eq!(state.current_player_info().position(), (5, 3));
+        assert_eq!(state.current_player_info().goal(), (5, 3));
+    }
+
+    #[test]
+    fn test_player_info_display() {
+        let info = FullPlayerInfo::new((3, 3), (1, 1), (5, 5), ColorName::Red.into());
+        assert_eq!(info.to_string(), "red@(1,1)→home(3,3) goal(5,5)");
+
+        let info = PlayerInfo::from(info);
+        assert_eq!(info.to_string(), "red@(1,1)→home(3,3)");
+
+        let info = PlayerInfo {
+            current: (0, 6),
+            home: (1, 5),
+            color: (170, 187, 204).into(),
+        };
+        assert_eq!(info.to_string(), "AABBCC@(0,6)→home(1,5)");
+    }
+
+    #[test]
+    fn test_player_move_display() {
+        let player_move = PlayerMove {
+            slide: Slide::new_unchecked(2, East),
+            rotations: 1,
+            destination: (1, 3),
+        };
+        assert_eq!(
+            player_move.to_string(),
+            "slide row 2 East, rotate 90°, move to (1,3)"
+        );
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let mut state = State::default();
+        state.player_info.push_back(FullPlayerInfo {
+            home: (1, 1),
+            position: (1, 1),
+            goal: (5, 5),
+            color: ColorName::Red.into(),
+            goals_reached: 0,
+            goals_history: vec![],
+        });
+        state.player_info.push_back(FullPlayerInfo {
+            home: (3, 1),
+            position: (0, 3),
+            goal: (1, 3),
+            color: ColorName::Blue.into(),
+            goals_reached: 0,
+            goals_history: vec![],
+        });
+        state.set_previous_slide(state.board.new_slide(2, East));
+        let original = state.clone();
+
+        for PlayerMove {
+            slide,
+            rotations,
+            destination,
+        } in original.legal_moves().into_iter().step_by(23)
+        {
+            let snap = state.snapshot();
+            state.try_move(slide, rotations, destination).unwrap();
+            let after_move = state.clone();
+
+            // nested snapshots are restored in reverse order
+            let next = state.legal_moves()[0];
+            let nested_snap = state.snapshot();
+            state
+                .try_move(next.slide, next.rotations, next.destination)
+                .unwrap();
+            state.restore(nested_snap).unwrap();
+            assert_eq!(state, after_move);
+
+            state.restore(snap).unwrap();
+            assert_eq!(state, original);
+        }
+
+        // restoring without sliding only undoes rotations and movement
+        let snap = state.snapshot();
+        state.rotate_spare(1);
+        state.player_info[0].position = (1, 3);
+        state.restore(snap).unwrap();
+        assert_eq!(state, original);
+
+        // restoring after two slides fails without changing the state
+        let snap = state.snapshot();
+        for _ in 0..2 {
+            let next = state.legal_moves()[0];
+            state
+                .try_move(next.slide, next.rotations, next.destination)
+                .unwrap();
+        }
+        let after_moves = state.clone();
+        assert!(matches!(
+            state.restore(snap),
+            Err(StateError::SnapshotMismatch)
+        ));
+        assert_eq!(state, after_moves);
+    }
+
+    #[test]
+    fn test_eq_unordered_players() {
+        let mut state = State::default();
+        state.add_player(FullPlayerInfo::new(
+            (1, 1),
+            (1, 1),
+            (3, 3),
+            ColorName::Red.into(),
+        ));
+        state.add_player(FullPlayerInfo::new(
+            (3, 1),
+            (0, 0),
+            (5, 5),
+            ColorName::Blue.into(),
+        ));
+
+        let mut rotated = state.clone();
+        rotated.next_player();
+        assert_ne!(state, rotated);
+        assert!(state.eq_unordered_players(&rotated));
+        assert!(rotated.eq_unordered_players(&state));
+
+        let mut moved = rotated.clone();
+        moved.current_player_info_mut().set_position((2, 2));
+        assert!(!state.eq_unordered_players(&moved));
+
+        let mut slid = rotated;
+        slid.slide_and_insert(Slide::new_unchecked(0, North))
+            .unwrap();
+        assert!(!state.eq_unordered_players(&slid));
+
+        let mut removed = state.clone();
+        removed.remove_player().unwrap();
+        assert!(!state.eq_unordered_players(&removed));
+        assert!(!removed.eq_unordered_players(&state));
+    }
+
+    #[test]
+    fn test_reachable_for() {
+        let mut state = State::default();
+        state.add_player(FullPlayerInfo::new(
+            (1, 1),
+            (1, 1),
+            (3, 3),
+            ColorName::Red.into(),
+        ));
+        state.add_player(FullPlayerInfo::new(
+            (3, 1),
+            (6, 0),
+            (5, 5),
+            ColorName::Blue.into(),
+        ));
+        state.add_player(FullPlayerInfo::new(
+            (5, 1),
+            (2, 4),
+            (1, 5),
+            ColorName::Green.into(),
+        ));
+        // Default Board<7> is:
+        //   0123456
+        // 0 ─│└┌┐┘┴
+        // 1 ├┬┤┼─│└
+        // 2 ┌┐┘┴├┬┤
+        // 3 ┼─│└┌┐┘
+        // 4 ┴├┬┤┼─│
+        // 5 └┌┐┘┴├┬
+        // 6 ┤┼─│└┌┐
+        //
+        // extra = ┼
+        let mut rotated = state.clone();
+        for idx in 0..3 {
+            let mut expected = rotated.reachable_by_player();
+            let mut reachable = state.reachable_for(idx).unwrap();
+            expected.sort_unstable();
+            reachable.sort_unstable();
+            assert_eq!(reachable, expected);
+            rotated.next_player();
+        }
+
+        assert!(matches!(
+            state.reachable_for(3),
+            Err(StateError::NoSuchPlayer(3))
+        ));
+    }
 }
@@ -2,7 +2,7 @@ use unordered_pair::UnorderedPair;
 
 use crate::gem::Gem;
 /// Represents a single tile on a board
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Tile {
     pub connector: ConnectorShape,
     pub gems: UnorderedPair<Gem>,
@@ -28,14 +28,14 @@ impl Tile {
 }
 
 /// This enum describes the two orientations for [`ConnectorShape::Path`]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PathOrientation {
     Horizontal,
     Vertical,
 }
 
 /// This enum describes the four orientations for [`ConnectorShape::Corner`] and [`ConnectorShape::Fork`]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CompassDirection {
     North,
     South,
@@ -76,6 +76,26 @@ impl CompassDirection {
         }
     }
 
+    /// Returns a rotated direction `n` 90 degree turns clockwise.
+    /// ```
+    /// # use common::tile::CompassDirection;
+    /// assert_eq!(CompassDirection::North.rotate_clockwise_n(2), CompassDirection::South);
+    /// ```
+    #[must_use]
+    pub fn rotate_clockwise_n(self, n: usize) -> Self {
+        (0..n % 4).fold(self, |dir, _| dir.rotate_clockwise())
+    }
+
+    /// Returns a rotated direction `n` 90 degree turns counter clockwise.
+    /// ```
+    /// # use common::tile::CompassDirection;
+    /// assert_eq!(CompassDirection::North.rotate_counter_clockwise_n(3), CompassDirection::East);
+    /// ```
+    #[must_use]
+    pub fn rotate_counter_clockwise_n(self, n: usize) -> Self {
+        (0..n % 4).fold(self, |dir, _| dir.rotate_counter_clockwise())
+    }
+
     /// Returns the opposite direction of the given direction
     /// ```
     ///# use common::tile::CompassDirection;
@@ -94,10 +114,43 @@ impl CompassDirection {
             West => East,
         }
     }
+
+    /// Does this direction run along a row?
+    /// ```
+    ///# use common::tile::CompassDirection;
+    /// assert!(CompassDirection::East.is_horizontal());
+    /// assert!(!CompassDirection::North.is_horizontal());
+    /// ```
+    pub fn is_horizontal(self) -> bool {
+        self.axis() == Axis::Horizontal
+    }
+
+    /// Returns the [`Axis`] this direction runs along.
+    /// ```
+    ///# use common::tile::{Axis, CompassDirection};
+    /// assert_eq!(CompassDirection::West.axis(), Axis::Horizontal);
+    /// assert_eq!(CompassDirection::South.axis(), Axis::Vertical);
+    /// ```
+    pub fn axis(self) -> Axis {
+        use CompassDirection::*;
+        match self {
+            East | West => Axis::Horizontal,
+            North | South => Axis::Vertical,
+        }
+    }
 }
 
-/// This type describes the connection type of a tile
+/// This enum describes whether a [`CompassDirection`] runs along a row or along a column
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    /// `East` and `West`, which move along a row
+    Horizontal,
+    /// `North` and `South`, which move along a column
+    Vertical,
+}
+
+/// This type describes the connection type of a tile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ConnectorShape {
     /// Path Can Only Be Horizontal Or Vertical  
     /// ─ - Horizontal  
@@ -157,11 +210,81 @@ impl ConnectorShape {
         )
     }
 
+    /// Returns every direction this [`ConnectorShape`] is open towards, in the order `North`,
+    /// `East`, `South`, `West`.
+    /// ```
+    ///# use common::tile::{CompassDirection::*, ConnectorShape};
+    /// assert_eq!(ConnectorShape::Fork(North).open_directions(), vec![North, East, West]);
+    /// assert_eq!(ConnectorShape::Crossroads.open_directions().len(), 4);
+    /// ```
+    pub fn open_directions(self) -> Vec<CompassDirection> {
+        use CompassDirection::*;
+        [North, East, South, West]
+            .into_iter()
+            .filter(|&dir| self.connected_to(dir))
+            .collect()
+    }
+
     /// Checks if `self` can connect to `other` in the given [`CompassDirection`].
     pub fn connected(self, other: Self, direction: CompassDirection) -> bool {
         self.connected_to(direction) && other.connected_to(direction.opposite())
     }
 
+    /// Returns the box-drawing character that looks like this `ConnectorShape`
+    ///
+    /// ```
+    /// use common::tile::{CompassDirection, ConnectorShape};
+    ///
+    /// assert_eq!(ConnectorShape::Corner(CompassDirection::North).to_char(), '└');
+    /// ```
+    pub fn to_char(self) -> char {
+        use CompassDirection::*;
+        use ConnectorShape::*;
+        use PathOrientation::*;
+        match self {
+            Path(Vertical) => '│',
+            Path(Horizontal) => '─',
+            Corner(North) => '└',
+            Corner(East) => '┌',
+            Corner(South) => '┐',
+            Corner(West) => '┘',
+            Fork(North) => '┴',
+            Fork(East) => '├',
+            Fork(South) => '┬',
+            Fork(West) => '┤',
+            Crossroads => '┼',
+        }
+    }
+
+    /// Returns the `ConnectorShape` drawn by the box-drawing character `c`, or `None` if `c` does
+    /// not draw a `ConnectorShape`
+    ///
+    /// ```
+    /// use common::tile::ConnectorShape;
+    ///
+    /// assert_eq!(ConnectorShape::from_char('┼'), Some(ConnectorShape::Crossroads));
+    /// assert_eq!(ConnectorShape::from_char('+'), None);
+    /// ```
+    pub fn from_char(c: char) -> Option<Self> {
+        use CompassDirection::*;
+        use ConnectorShape::*;
+        use PathOrientation::*;
+        Some(match c {
+            '│' => Path(Vertical),
+            '─' => Path(Horizontal),
+            '└' => Corner(North),
+            '┌' => Corner(East),
+            '┐' => Corner(South),
+            '┘' => Corner(West),
+            '┴' => Fork(North),
+            '├' => Fork(East),
+            '┬' => Fork(South),
+            '┤' => Fork(West),
+            '┼' => Crossroads,
+            _ => return None,
+        })
+    }
+
     pub fn from_num(num: usize) -> Self {
         use CompassDirection::*;
         use ConnectorShape::*;
@@ -190,6 +313,40 @@ mod tile_tests {
     use ConnectorShape::*;
     use PathOrientation::*;
 
+    #[test]
+    pub fn connector_shape_chars() {
+        assert_eq!(ConnectorShape::from_char('┼'), Some(Crossroads));
+        assert_eq!(Corner(North).to_char(), '└');
+        assert_eq!(ConnectorShape::from_char('x'), None);
+        assert_eq!(ConnectorShape::from_char(' '), None);
+
+        let shapes = (0..11).map(ConnectorShape::from_num).collect::<Vec<_>>();
+        for shape in &shapes {
+            assert_eq!(ConnectorShape::from_char(shape.to_char()), Some(*shape));
+        }
+        let chars = shapes
+            .iter()
+            .map(|shape| shape.to_char())
+            .collect::<String>();
+        assert_eq!(chars, "─│└┌┐┘┴├┬┤┼");
+    }
+
+    #[test]
+    pub fn compass_direction_axis() {
+        assert!(East.is_horizontal());
+        assert!(West.is_horizontal());
+        assert!(!North.is_horizontal());
+        assert!(!South.is_horizontal());
+        assert_eq!(East.axis(), Axis::Horizontal);
+        assert_eq!(West.axis(), Axis::Horizontal);
+        assert_eq!(North.axis(), Axis::Vertical);
+        assert_eq!(South.axis(), Axis::Vertical);
+        for dir in [North, South, East, West] {
+            assert_eq!(dir.axis(), dir.opposite().axis());
+            assert_ne!(dir.axis(), dir.rotate_clockwise().axis());
+        }
+    }
+
     #[test]
     pub fn compass_direction_rotate() {
         assert_eq!(North.rotate_clockwise(), East);
@@ -198,6 +355,24 @@ mod tile_tests {
         assert_eq!(West.rotate_clockwise(), North);
     }
 
+    #[test]
+    pub fn compass_direction_rotate_n() {
+        assert_eq!(North.rotate_clockwise_n(0), North);
+        assert_eq!(North.rotate_clockwise_n(2), South);
+        assert_eq!(North.rotate_clockwise_n(4), North);
+        assert_eq!(West.rotate_clockwise_n(5), North);
+        for dir in [North, South, East, West] {
+            assert_eq!(
+                dir.rotate_counter_clockwise_n(3),
+                dir.rotate_counter_clockwise()
+                    .rotate_counter_clockwise()
+                    .rotate_counter_clockwise()
+            );
+            assert_eq!(dir.rotate_counter_clockwise_n(6), dir.opposite());
+            assert_eq!(dir.rotate_counter_clockwise_n(1), dir.rotate_clockwise_n(3));
+        }
+    }
+
     #[test]
     pub fn connector_rotate() {
         assert_eq!(Crossroads.rotate(), Crossroads);
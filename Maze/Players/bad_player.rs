@@ -2,7 +2,7 @@ use std::{cell::RefCell, thread, time::Duration};
 
 use anyhow::anyhow;
 use common::{
-    board::Board,
+    board::{Board, Slide},
     grid::Position,
     json::Name,
     state::{PlayerInfo, State},
@@ -11,7 +11,7 @@ use serde::Deserialize;
 
 use crate::{
     player::{PlayerApi, PlayerApiResult},
-    strategy::PlayerAction,
+    strategy::{PlayerAction, PlayerMove},
 };
 
 #[derive(Debug, Deserialize, PartialEq, Eq)]
@@ -20,6 +20,8 @@ pub enum BadFM {
     SetUp,
     TakeTurn,
     Win,
+    /// Takes turns with a `PlayerMove` that is never legal, because it undoes the previous slide
+    UndoSlide,
 }
 
 pub struct BadPlayer {
@@ -31,6 +33,26 @@ impl BadPlayer {
     pub fn new(player: Box<dyn PlayerApi>, bad_fm: BadFM) -> Self {
         Self { bad_fm, player }
     }
+
+    /// Returns a `PlayerMove` that is never legal in `state`. It undoes the previous slide if there
+    /// is one. Otherwise, it makes the first slide in `Board::all_slides` and moves the active
+    /// player to where that slide leaves them, which is not a move.
+    fn illegal_move(state: &State<PlayerInfo>) -> PlayerMove {
+        let slide = match state.previous_slide {
+            Some(prev) => Slide::new_unchecked(prev.index, prev.direction.opposite()),
+            None => state.board.all_slides()[0],
+        };
+        let destination = slide.move_position(
+            state.player_info[0].current,
+            state.board.num_cols(),
+            state.board.num_rows(),
+        );
+        PlayerMove {
+            slide,
+            rotations: 0,
+            destination,
+        }
+    }
 }
 
 impl PlayerApi for BadPlayer {
@@ -57,6 +79,9 @@ impl PlayerApi for BadPlayer {
                 .checked_div(0)
                 .ok_or_else(|| anyhow!("tried to divide by 0"))?;
         }
+        if let BadFM::UndoSlide = self.bad_fm {
+            return Ok(Some(Self::illegal_move(&state)));
+        }
         self.player.take_turn(state)
     }
 
@@ -68,6 +93,10 @@ impl PlayerApi for BadPlayer {
         }
         self.player.won(did_win)
     }
+
+    fn game_start(&mut self, num_players: usize) {
+        self.player.game_start(num_players)
+    }
 }
 
 pub struct BadPlayerLoop {
@@ -86,11 +115,17 @@ impl BadPlayerLoop {
             times_called: RefCell::new(0),
         }
     }
-    /// If times_called
-    fn inc_or_loop(&self) {
+    /// Counts a call to the method that misbehaves, and returns `true` if it is the `times`th
+    /// call, which is when that method should misbehave.
+    fn inc_times_called(&self) -> bool {
         let mut tc = self.times_called.borrow_mut();
         *tc += 1;
-        if *tc == self.times {
+        *tc == self.times
+    }
+
+    /// If times_called
+    fn inc_or_loop(&self) {
+        if self.inc_times_called() {
             loop {
                 thread::sleep(Duration::from_secs(2));
             }
@@ -118,6 +153,10 @@ impl PlayerApi for BadPlayerLoop {
         if let BadFM::TakeTurn = self.badfm {
             self.inc_or_loop();
         }
+        // the `times`th turn undoes the previous slide instead of looping
+        if self.badfm == BadFM::UndoSlide && self.inc_times_called() {
+            return Ok(Some(BadPlayer::illegal_move(&state)));
+        }
         self.api.take_turn(state)
     }
 
@@ -127,4 +166,8 @@ impl PlayerApi for BadPlayerLoop {
         }
         self.api.won(did_win)
     }
+
+    fn game_start(&mut self, num_players: usize) {
+        self.api.game_start(num_players)
+    }
 }
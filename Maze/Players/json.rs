@@ -6,7 +6,9 @@ use common::json::{Coordinate, Index, JsonDegree, JsonDirection, JsonError};
 use serde::ser::SerializeTuple;
 use serde::{de, Deserialize, Deserializer, Serialize};
 
-use crate::strategy::{NaiveStrategy, PlayerAction, PlayerMove};
+use crate::strategy::{
+    strategy_from_name, BoundedStrategy, NaiveStrategy, PlayerAction, PlayerMove, Strategy,
+};
 
 /// Describes either a `Reimann` or a `Euclid` strategy
 #[derive(Debug, Deserialize)]
@@ -24,6 +26,121 @@ impl From<JsonStrategyDesignation> for NaiveStrategy {
     }
 }
 
+/// Describes a `Strategy` and its parameters. This is either the bare name of a `Strategy`, like
+/// `"Euclid"`, or an object with a `name` and optional parameters, like
+/// `{ "name": "Euclid", "max_candidates": 5 }`.
+///
+/// `name` is looked up with `strategy_from_name`. Parameters that no `Strategy` uses, like a
+/// search `depth`, are ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrategyConfig {
+    pub name: String,
+    /// If set, the `Strategy` evaluates at most this many alternative destinations, as a
+    /// `BoundedStrategy`.
+    pub max_candidates: Option<usize>,
+}
+
+impl StrategyConfig {
+    /// Returns the configured `Strategy`, or `None` if no `Strategy` is named `self.name`.
+    pub fn into_strategy(self) -> Option<Box<dyn Strategy + Send>> {
+        let strategy = strategy_from_name(&self.name)?;
+        Some(match self.max_candidates {
+            Some(max_candidates) => Box::new(BoundedStrategy::new(strategy, max_candidates)),
+            None => strategy,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for StrategyConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct JsonStrategyObject {
+            name: String,
+            #[serde(default)]
+            max_candidates: Option<usize>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum MaybeConfig {
+            Name(String),
+            Object(JsonStrategyObject),
+        }
+
+        Ok(match MaybeConfig::deserialize(deserializer)? {
+            MaybeConfig::Name(name) => StrategyConfig {
+                name,
+                max_candidates: None,
+            },
+            MaybeConfig::Object(JsonStrategyObject {
+                name,
+                max_candidates,
+            }) => StrategyConfig {
+                name,
+                max_candidates,
+            },
+        })
+    }
+}
+
+#[test]
+fn test_strategy_config() {
+    use common::state::{PlayerInfo, State};
+
+    let bare: StrategyConfig = serde_json::from_str("\"Riemann\"").unwrap();
+    assert_eq!(
+        bare,
+        StrategyConfig {
+            name: String::from("Riemann"),
+            max_candidates: None,
+        }
+    );
+
+    let object: StrategyConfig =
+        serde_json::from_str("{ \"name\": \"Riemann\", \"max_candidates\": 0 }").unwrap();
+    assert_eq!(
+        object,
+        StrategyConfig {
+            name: String::from("Riemann"),
+            max_candidates: Some(0),
+        }
+    );
+    let object: StrategyConfig = serde_json::from_str("{ \"name\": \"Euclid\" }").unwrap();
+    assert_eq!(object.max_candidates, None);
+
+    // on the default board, (3, 3) cannot be reached from (0, 0), so a strategy that may not try
+    // any alternative destinations passes
+    let state: State<PlayerInfo> = State::default();
+    assert!(bare
+        .into_strategy()
+        .unwrap()
+        .get_move(state.clone(), (0, 0), (3, 3))
+        .is_some());
+    let bounded: StrategyConfig =
+        serde_json::from_str("{ \"name\": \"Riemann\", \"max_candidates\": 0 }").unwrap();
+    assert!(bounded
+        .into_strategy()
+        .unwrap()
+        .get_move(state, (0, 0), (3, 3))
+        .is_none());
+
+    let unknown: StrategyConfig = serde_json::from_str("\"Dijkstra\"").unwrap();
+    assert!(unknown.into_strategy().is_none());
+    let object: StrategyConfig =
+        serde_json::from_str("{ \"name\": \"Euclid\", \"depth\": 2 }").unwrap();
+    assert_eq!(
+        object,
+        StrategyConfig {
+            name: String::from("Euclid"),
+            max_candidates: None,
+        }
+    );
+    assert!(serde_json::from_str::<StrategyConfig>("{ \"max_candidates\": 2 }").is_err());
+}
+
 /// Describes a choice a player can make for their action
 /// A `Pass` is a player passing their turn
 /// A `Move` contains the `Index` of the row/col being slid, the `JsonDirection` of the slide, a
@@ -141,6 +258,8 @@ impl JsonChoice {
     }
 }
 
+/// Rotating the spare tile 4 times leaves it unchanged, so `rotations` is always sent as one of
+/// 0, 90, 180 or 270 degrees.
 impl From<PlayerAction> for JsonChoice {
     fn from(pa: PlayerAction) -> Self {
         match pa {
@@ -152,9 +271,40 @@ impl From<PlayerAction> for JsonChoice {
             }) => JsonChoice::Move(
                 Index(slide.index),
                 slide.direction.into(),
-                JsonDegree(rotations * 90),
+                JsonDegree(rotations % 4 * 90),
                 destination.into(),
             ),
         }
     }
 }
+
+#[test]
+fn test_json_choice_degrees() {
+    use common::board::DefaultBoard;
+    use common::tile::CompassDirection;
+
+    let board: Board = DefaultBoard::<7, 7>::default_board();
+    let slide = Slide::new_unchecked(2, CompassDirection::East);
+    for (rotations, degree) in [(0, 0), (1, 90), (2, 180), (3, 270), (7, 270)] {
+        let player_move = PlayerMove {
+            slide,
+            rotations,
+            destination: (1, 3),
+        };
+        let choice = JsonChoice::from(Some(player_move));
+        let json = serde_json::to_string(&choice).unwrap();
+        assert_eq!(
+            json,
+            format!("[2,\"RIGHT\",{degree},{{\"row#\":3,\"column#\":1}}]")
+        );
+
+        let parsed: JsonChoice = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            parsed.try_into_action(&board).unwrap(),
+            Some(PlayerMove {
+                rotations: rotations % 4,
+                ..player_move
+            })
+        );
+    }
+}
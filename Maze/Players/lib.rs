@@ -3,6 +3,11 @@
 //! Contains the trait [`player::PlayerApi`] and an implementation of it [`LocalPlayer`]. This
 //! implementation relies on the strategies in [`strategy`] to decide its turns.
 //!
+//! Also contains [`player::LoggingPlayer`], which wraps any [`player::PlayerApi`] and logs every
+//! call made to it.
+//! [`player::StrategyRunner`] runs a strategy without a player around it, checking that its moves
+//! are legal.
+//!
 //! ## Strategy
 //! Within this module are the data definitions for the decisions a player can make in terms of
 //! making a move.
@@ -11,7 +16,12 @@
 //! decision on a move given a state.
 //!
 //! Also contains an implementation that relies on enumerating alternate goals if the player's is
-//! unreachable and trying to reach those in an order defined in its implementation.
+//! unreachable and trying to reach those in an order defined in its implementation, and
+//! [`strategy::BoundedStrategy`] which caps how many of those alternate goals are tried.
+//! [`strategy::CustomStrategy`] tries the alternate goals in an order given by the caller.
+//! [`strategy::AvoidOpponentHomesStrategy`] keeps another strategy from ending turns on other
+//! players' homes.
+//! Strategies can be looked up by name with [`strategy::strategy_from_name`].
 //!
 //! ## Bad Player
 //! Contains a few implementations of [`player::PlayerApi`] that intentionaly misbehave for testing
@@ -19,7 +29,8 @@
 //!
 //! ### Json
 //! Contains data definitions for Json for integration tests but also most importantly
-//! [`json::JsonChoice`] which is the data definition for a move sent over the network
+//! [`json::JsonChoice`] which is the data definition for a move sent over the network.
+//! [`json::StrategyConfig`] describes a strategy and its parameters, by name or as an object.
 
 /// Contains implementations of players that misbehave
 pub mod bad_player;
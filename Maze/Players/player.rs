@@ -1,6 +1,6 @@
 use std::io;
 
-use crate::strategy::{PlayerAction, Strategy};
+use crate::strategy::{strategy_from_name, PlayerAction, PlayerMove, Strategy};
 use common::{
     board::{Board, DefaultBoard},
     grid::Position,
@@ -11,20 +11,50 @@ use thiserror::Error;
 
 pub type PlayerApiResult<T> = Result<T, PlayerApiError>;
 
+/// Describes the ways calling a `PlayerApi` method can fail.
+///
+/// Failures of the remote protocol itself, such as a remote player not responding in time or
+/// sending malformed Json, are reported as `PlayerApiError::Remote`. The other variants come from
+/// the layers around it.
 #[derive(Error, Debug)]
 pub enum PlayerApiError {
     #[error("IO error occured when communicating to player")]
     IoError(#[from] io::Error),
+    /// A message to the player could not be serialized. Malformed responses from a remote player
+    /// are `RemoteError::Malformed` instead.
     #[error("response is not Json")]
     NotJson(#[from] serde_json::Error),
     #[error("response has incorrect format")]
     WrongJson(#[from] JsonError),
+    /// The `Referee` gave up waiting on a player, local or remote. A remote player whose
+    /// connection times out before that is `RemoteError::Timeout` instead.
     #[error("timeout reached when attempting to recieve a response")]
     Timeout,
     #[error(transparent)]
+    Remote(#[from] RemoteError),
+    #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
 
+/// Describes the ways communicating with a player over the remote protocol can fail
+#[derive(Error, Debug)]
+pub enum RemoteError {
+    /// The connection to the remote player timed out while reading a response.
+    #[error("timeout reached when waiting for the remote player to respond")]
+    Timeout,
+    #[error("the remote player closed the connection")]
+    ConnectionClosed,
+    #[error("the remote player sent malformed Json")]
+    Malformed(#[source] serde_json::Error),
+    #[error("the remote player responded with an unexpected result")]
+    UnexpectedResult,
+    #[error("the remote player sent an invalid name: {0:?}")]
+    InvalidName(String),
+    /// The remote protocol has no function call for this method, so the player is never asked.
+    #[error("the remote protocol has no call for {0}")]
+    Unsupported(&'static str),
+}
+
 /// Trait describing the methods that `Player`s must implement
 pub trait PlayerApi: Send {
     /// Returns the name of this Player
@@ -38,6 +68,9 @@ pub trait PlayerApi: Send {
     fn take_turn(&self, state: State<PlayerInfo>) -> PlayerApiResult<PlayerAction>;
     /// The player is informed if they won or not.
     fn won(&mut self, did_win: bool) -> PlayerApiResult<()>;
+    /// The player is told that the game is starting with `num_players` players, once, after every
+    /// player has been set up and before the first turn.
+    fn game_start(&mut self, _num_players: usize) {}
 }
 
 /// Represents a Local AI Player
@@ -61,6 +94,14 @@ impl<S: Strategy + Send> LocalPlayer<S> {
     }
 }
 
+impl LocalPlayer<Box<dyn Strategy + Send>> {
+    /// Creates a `LocalPlayer` using the `Strategy` registered under `strategy_name`, or `None` if
+    /// there is no such `Strategy`. See `strategy::strategy_from_name`.
+    pub fn from_strategy_name(name: Name, strategy_name: &str) -> Option<Self> {
+        strategy_from_name(strategy_name).map(|strategy| Self::new(name, strategy))
+    }
+}
+
 impl<S: Strategy + Send> PlayerApi for LocalPlayer<S> {
     fn name(&self) -> Name {
         self.name.clone()
@@ -98,12 +139,147 @@ impl<S: Strategy + Send> PlayerApi for LocalPlayer<S> {
     }
 }
 
+/// The error returned by `StrategyRunner::run` when its `Strategy` chooses a move that is not
+/// legal in the given `State`
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("the strategy chose an illegal move: {0}")]
+pub struct IllegalMove(pub PlayerMove);
+
+/// Runs a `Strategy` on its own, without a `LocalPlayer` around it, checking that every move it
+/// chooses is legal. Useful for testing strategies in isolation.
+///
+/// Goals that are not given to `run` fall back to `fallback_goal`.
+pub struct StrategyRunner<S: Strategy> {
+    pub strategy: S,
+    pub fallback_goal: Position,
+}
+
+impl<S: Strategy> StrategyRunner<S> {
+    pub fn new(strategy: S, fallback_goal: Position) -> Self {
+        Self {
+            strategy,
+            fallback_goal,
+        }
+    }
+
+    /// Returns the `PlayerAction` `self.strategy` chooses for the active player in `state`, who is
+    /// on `start` and heading to `goal`, or `self.fallback_goal` if `goal` is `None`.
+    ///
+    /// # Errors
+    /// Returns an error if the chosen move is not valid according to `State::is_valid_move`.
+    pub fn run(
+        &self,
+        state: State<PlayerInfo>,
+        start: Position,
+        goal: Option<Position>,
+    ) -> Result<PlayerAction, IllegalMove> {
+        let goal = goal.unwrap_or(self.fallback_goal);
+        match self.strategy.get_move(state.clone(), start, goal) {
+            Some(mv) if !state.is_valid_move(mv.slide, mv.rotations, mv.destination) => {
+                Err(IllegalMove(mv))
+            }
+            action => Ok(action),
+        }
+    }
+}
+
+/// Wraps a `PlayerApi`, writing a summary of every call made to it and its result to `sink`
+/// before returning that result. The wrapped player is otherwise unchanged.
+pub struct LoggingPlayer<P: PlayerApi> {
+    /// The `PlayerApi` every call is delegated to
+    player: P,
+    /// Recieves one entry for every call made to `player`
+    sink: Box<dyn Fn(String) + Send>,
+}
+
+impl<P: PlayerApi> LoggingPlayer<P> {
+    pub fn new(player: P, sink: impl Fn(String) + Send + 'static) -> Self {
+        Self {
+            player,
+            sink: Box::new(sink),
+        }
+    }
+
+    /// Sends an entry for `call` to `self.sink`, using `describe` to summarize a successful
+    /// `result`
+    fn log<T>(
+        &self,
+        call: String,
+        result: &PlayerApiResult<T>,
+        describe: impl FnOnce(&T) -> String,
+    ) {
+        let outcome = match result {
+            Ok(val) => describe(val),
+            Err(err) => format!("error: {err}"),
+        };
+        (self.sink)(format!("{}: {call} -> {outcome}", self.player.name()));
+    }
+}
+
+impl<P: PlayerApi> PlayerApi for LoggingPlayer<P> {
+    fn name(&self) -> Name {
+        self.player.name()
+    }
+
+    fn propose_board0(&self, cols: u32, rows: u32) -> PlayerApiResult<Board> {
+        let result = self.player.propose_board0(cols, rows);
+        self.log(
+            format!("propose_board0({cols}, {rows})"),
+            &result,
+            |board| format!("{}x{} board", board.num_cols(), board.num_rows()),
+        );
+        result
+    }
+
+    fn setup(&mut self, state: Option<State<PlayerInfo>>, goal: Position) -> PlayerApiResult<()> {
+        let call = format!(
+            "setup({}, goal ({},{}))",
+            if state.is_some() { "state" } else { "no state" },
+            goal.0,
+            goal.1
+        );
+        let result = self.player.setup(state, goal);
+        self.log(call, &result, |_| "ok".to_string());
+        result
+    }
+
+    fn take_turn(&self, state: State<PlayerInfo>) -> PlayerApiResult<PlayerAction> {
+        let call = match state.player_info.front() {
+            Some(info) => format!("take_turn(at ({},{}))", info.current.0, info.current.1),
+            None => "take_turn(no players)".to_string(),
+        };
+        let result = self.player.take_turn(state);
+        self.log(call, &result, |action| match action {
+            Some(player_move) => player_move.to_string(),
+            None => "pass".to_string(),
+        });
+        result
+    }
+
+    fn won(&mut self, did_win: bool) -> PlayerApiResult<()> {
+        let result = self.player.won(did_win);
+        self.log(format!("won({did_win})"), &result, |_| "ok".to_string());
+        result
+    }
+
+    fn game_start(&mut self, num_players: usize) {
+        self.player.game_start(num_players);
+        self.log(format!("game_start({num_players})"), &Ok(()), |_| {
+            "ok".to_string()
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::sync::{Arc, Mutex};
+
     use common::color::ColorName;
 
     use super::*;
 
+    use common::tile::CompassDirection;
+
     use crate::{
         player::PlayerApi,
         strategy::{NaiveStrategy, Strategy},
@@ -185,4 +361,122 @@ mod tests {
         let turn = player.take_turn(state.clone()).unwrap();
         assert_eq!(turn, NaiveStrategy::Euclid.get_move(state, (0, 0), (1, 1)));
     }
+
+    #[test]
+    fn test_from_strategy_name() {
+        let mut player =
+            LocalPlayer::from_strategy_name(Name::from_static("bill"), "Manhattan").unwrap();
+        assert_eq!(player.name(), Name::from_static("bill"));
+        player.setup(None, (1, 1)).unwrap();
+
+        let state = State {
+            player_info: vec![PlayerInfo {
+                current: (0, 0),
+                home: (0, 0),
+                color: ColorName::Red.into(),
+            }]
+            .into(),
+            ..Default::default()
+        };
+        let turn = player.take_turn(state.clone()).unwrap();
+        assert_eq!(
+            turn,
+            NaiveStrategy::Manhattan.get_move(state, (0, 0), (1, 1))
+        );
+
+        assert!(LocalPlayer::from_strategy_name(Name::from_static("bill"), "Nope").is_none());
+    }
+
+    #[test]
+    fn test_strategy_runner() {
+        let state = State {
+            player_info: vec![PlayerInfo {
+                current: (0, 0),
+                home: (0, 0),
+                color: ColorName::Red.into(),
+            }]
+            .into(),
+            ..Default::default()
+        };
+
+        let runner = StrategyRunner::new(NaiveStrategy::Euclid, (1, 3));
+        let expected = NaiveStrategy::Euclid.get_move(state.clone(), (0, 0), (1, 3));
+        assert_eq!(
+            expected,
+            Some(PlayerMove {
+                slide: state.board.new_slide(0, CompassDirection::East).unwrap(),
+                rotations: 0,
+                destination: (2, 2),
+            })
+        );
+        assert_eq!(
+            runner.run(state.clone(), (0, 0), Some((1, 3))),
+            Ok(expected)
+        );
+        assert_eq!(runner.run(state.clone(), (0, 0), None), Ok(expected));
+
+        /// Always moves back to where the slide leaves the active player, which is never legal
+        struct StayStrategy;
+
+        impl Strategy for StayStrategy {
+            fn get_move(
+                &self,
+                state: State<PlayerInfo>,
+                start: Position,
+                _goal_tile: Position,
+            ) -> PlayerAction {
+                let slide = state.board.new_slide(0, CompassDirection::North).unwrap();
+                Some(PlayerMove {
+                    slide,
+                    rotations: 0,
+                    destination: slide.move_position(
+                        start,
+                        state.board.num_cols(),
+                        state.board.num_rows(),
+                    ),
+                })
+            }
+        }
+
+        let runner = StrategyRunner::new(StayStrategy, (1, 3));
+        let illegal = StayStrategy
+            .get_move(state.clone(), (0, 0), (1, 3))
+            .unwrap();
+        assert_eq!(runner.run(state, (0, 0), None), Err(IllegalMove(illegal)));
+    }
+
+    #[test]
+    fn test_logging_player() {
+        let log = Arc::new(Mutex::new(vec![]));
+        let sink_log = log.clone();
+        let mut player = LoggingPlayer::new(
+            LocalPlayer::new(Name::from_static("bill"), NaiveStrategy::Euclid),
+            move |entry| sink_log.lock().unwrap().push(entry),
+        );
+        assert_eq!(player.name(), Name::from_static("bill"));
+
+        player.setup(None, (1, 1)).unwrap();
+        let state = State {
+            player_info: vec![PlayerInfo {
+                current: (0, 0),
+                home: (0, 0),
+                color: ColorName::Red.into(),
+            }]
+            .into(),
+            ..Default::default()
+        };
+        let turn = player.take_turn(state.clone()).unwrap();
+        assert_eq!(turn, NaiveStrategy::Euclid.get_move(state, (0, 0), (1, 1)));
+        player.won(false).unwrap();
+
+        let action = turn.map_or("pass".to_string(), |player_move| player_move.to_string());
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "bill: setup(no state, goal (1,1)) -> ok".to_string(),
+                format!("bill: take_turn(at (0,0)) -> {action}"),
+                "bill: won(false) -> ok".to_string(),
+            ]
+        );
+    }
 }
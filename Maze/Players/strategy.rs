@@ -1,6 +1,6 @@
 use clap::ValueEnum;
+pub use common::state::PlayerMove;
 use common::{
-    board::Slide,
     grid::{squared_euclidian_distance, Position},
     state::{PlayerInfo, State},
     tile::CompassDirection,
@@ -17,6 +17,20 @@ pub trait Strategy {
         start: Position,
         goal_tile: Position,
     ) -> PlayerAction;
+
+    /// This returns a valid move given the game state, evaluating at most `max_candidates`
+    /// alternative destinations if `goal_tile` cannot be reached.
+    ///
+    /// Strategies that do not search alternative destinations can ignore `max_candidates`.
+    fn get_bounded_move(
+        &self,
+        state: State<PlayerInfo>,
+        start: Position,
+        goal_tile: Position,
+        _max_candidates: usize,
+    ) -> PlayerAction {
+        self.get_move(state, start, goal_tile)
+    }
 }
 
 /// This type represents a possible player action  
@@ -24,17 +38,6 @@ pub trait Strategy {
 /// `Some(PlayerMove)` -> A move  
 pub type PlayerAction = Option<PlayerMove>;
 
-/// This type represents all the data needed to execute a move
-///
-/// # Warning
-/// This type does not self-validate because it has no knowledge of the board it will be played on.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct PlayerMove {
-    pub slide: Slide,
-    pub rotations: usize,
-    pub destination: Position,
-}
-
 #[derive(ValueEnum, Debug, Clone, Copy)]
 /// Implements a strategy that after failing to find a move directly to the goal tile, checks
 /// every other board position as a location to move. The order in which it checks every location
@@ -45,6 +48,21 @@ pub enum NaiveStrategy {
     Euclid,
     /// This variant sorts the posssible alternative goals in order of row-column order.
     Riemann,
+    /// This variant sorts the posssible alternative goals in order of smallest to largest
+    /// manhattan distance. It breaks any ties by picking the first one in row-column order.
+    Manhattan,
+}
+
+/// Returns the `Strategy` registered under `name`, or `None` if no `Strategy` has that name.
+///
+/// Every `Strategy` that can be selected by name is registered here.
+pub fn strategy_from_name(name: &str) -> Option<Box<dyn Strategy + Send>> {
+    match name {
+        "Euclid" => Some(Box::new(NaiveStrategy::Euclid)),
+        "Riemann" => Some(Box::new(NaiveStrategy::Riemann)),
+        "Manhattan" => Some(Box::new(NaiveStrategy::Manhattan)),
+        _ => None,
+    }
 }
 
 fn row_col_order(p1: &Position, p2: &Position) -> Ordering {
@@ -69,9 +87,22 @@ impl NaiveStrategy {
         state: &State<PlayerInfo>,
         start: Position,
         goal_tile: Position,
+    ) -> PlayerAction {
+        self.find_move_to_reach_first_alt_goals(state, start, goal_tile, usize::MAX)
+    }
+
+    /// Like `find_move_to_reach_alt_goal`, but only tries to reach the first `max_candidates`
+    /// alternative goals.
+    fn find_move_to_reach_first_alt_goals(
+        &self,
+        state: &State<PlayerInfo>,
+        start: Position,
+        goal_tile: Position,
+        max_candidates: usize,
     ) -> PlayerAction {
         self.get_alt_goals(goal_tile, state)
             .into_iter()
+            .take(max_candidates)
             .find_map(|goal| self.find_move_to_reach(state, start, goal))
     }
 
@@ -93,68 +124,91 @@ impl NaiveStrategy {
                 }
             }),
             Self::Riemann => Box::new(row_col_order),
+            Self::Manhattan => Box::new(|p1: &Position, p2: &Position| -> Ordering {
+                let manhattan1 = p1.0.abs_diff(goal_tile.0) + p1.1.abs_diff(goal_tile.1);
+                let manhattan2 = p2.0.abs_diff(goal_tile.0) + p2.1.abs_diff(goal_tile.1);
+                match manhattan1.cmp(&manhattan2) {
+                    Ordering::Equal => row_col_order(p1, p2),
+                    rest => rest,
+                }
+            }),
         };
 
-        let mut possible_goals: Vec<Position> = (0..board_state.board.num_rows())
-            .cartesian_product(0..board_state.board.num_cols())
-            .collect();
-        possible_goals.sort_by(alternative_goal_order);
-        possible_goals
+        sorted_alt_goals(board_state, alternative_goal_order)
     }
 
-    fn find_move_to_reach_helper<const N: usize>(
+    fn find_move_to_reach(
         &self,
         state: &State<PlayerInfo>,
-        lines: impl Iterator<Item = usize>,
-        directions: [CompassDirection; N],
         start: Position,
         destination: Position,
     ) -> PlayerAction {
-        for line in lines {
-            for direction in directions {
-                for rotations in 0..4 {
-                    if let Some(lslide) = state.previous_slide {
-                        if lslide.index == line && lslide.direction.opposite() == direction {
-                            continue;
-                        }
-                    }
-                    let slide = state.board.new_slide(line, direction).unwrap();
-                    if state.reachable_after_move(slide, rotations, destination, start) {
-                        return Some(PlayerMove {
-                            slide,
-                            rotations,
-                            destination,
-                        });
-                    }
+        find_move_to_reach(state, start, destination)
+    }
+}
+
+/// Returns every position on the board of `board_state`, sorted by `alternative_goal_order`.
+fn sorted_alt_goals(
+    board_state: &State<PlayerInfo>,
+    alternative_goal_order: impl FnMut(&Position, &Position) -> Ordering,
+) -> Vec<Position> {
+    let mut possible_goals: Vec<Position> = (0..board_state.board.num_rows())
+        .cartesian_product(0..board_state.board.num_cols())
+        .collect();
+    possible_goals.sort_by(alternative_goal_order);
+    possible_goals
+}
+
+fn find_move_to_reach_helper<const N: usize>(
+    state: &State<PlayerInfo>,
+    lines: impl Iterator<Item = usize>,
+    directions: [CompassDirection; N],
+    start: Position,
+    destination: Position,
+) -> PlayerAction {
+    for line in lines {
+        for direction in directions {
+            for rotations in 0..4 {
+                let slide = state.board.new_slide(line, direction).unwrap();
+                if state.slide_is_undo(slide) {
+                    continue;
+                }
+                if state.reachable_after_move(slide, rotations, destination, start) {
+                    return Some(PlayerMove {
+                        slide,
+                        rotations,
+                        destination,
+                    });
                 }
             }
         }
-        None
     }
+    None
+}
 
-    fn find_move_to_reach(
-        &self,
-        state: &State<PlayerInfo>,
-        start: Position,
-        destination: Position,
-    ) -> PlayerAction {
-        self.find_move_to_reach_helper(
+/// Returns the first move that lets a player on `start` reach `destination`, trying every row
+/// slide before any column slide.
+fn find_move_to_reach(
+    state: &State<PlayerInfo>,
+    start: Position,
+    destination: Position,
+) -> PlayerAction {
+    find_move_to_reach_helper(
+        state,
+        state.board.slideable_rows(),
+        [CompassDirection::West, CompassDirection::East],
+        start,
+        destination,
+    )
+    .or_else(|| {
+        find_move_to_reach_helper(
             state,
-            state.board.slideable_rows(),
-            [CompassDirection::West, CompassDirection::East],
+            state.board.slideable_cols(),
+            [CompassDirection::North, CompassDirection::South],
             start,
             destination,
         )
-        .or_else(|| {
-            self.find_move_to_reach_helper(
-                state,
-                state.board.slideable_cols(),
-                [CompassDirection::North, CompassDirection::South],
-                start,
-                destination,
-            )
-        })
-    }
+    })
 }
 
 impl Strategy for NaiveStrategy {
@@ -167,6 +221,203 @@ impl Strategy for NaiveStrategy {
         self.find_move_to_reach(&state, start, goal_tile)
             .or_else(|| self.find_move_to_reach_alt_goal(&state, start, goal_tile))
     }
+
+    fn get_bounded_move(
+        &self,
+        state: State<PlayerInfo>,
+        start: Position,
+        goal_tile: Position,
+        max_candidates: usize,
+    ) -> PlayerAction {
+        self.find_move_to_reach(&state, start, goal_tile)
+            .or_else(|| {
+                self.find_move_to_reach_first_alt_goals(&state, start, goal_tile, max_candidates)
+            })
+    }
+}
+
+/// A `Strategy` that searches alternative goals like `NaiveStrategy`, but in the order given by
+/// `goal_order` instead of a fixed one.
+///
+/// `goal_order` compares two alternative goals given the position of the goal tile.
+pub struct CustomStrategy {
+    #[allow(clippy::type_complexity)]
+    pub goal_order: Box<dyn Fn(&Position, &Position, Position) -> Ordering + Send>,
+}
+
+impl CustomStrategy {
+    pub fn new(
+        goal_order: impl Fn(&Position, &Position, Position) -> Ordering + Send + 'static,
+    ) -> Self {
+        Self {
+            goal_order: Box::new(goal_order),
+        }
+    }
+
+    /// Returns every position on the board sorted by `self.goal_order`.
+    fn get_alt_goals(&self, goal_tile: Position, board_state: &State<PlayerInfo>) -> Vec<Position> {
+        sorted_alt_goals(board_state, |p1, p2| (self.goal_order)(p1, p2, goal_tile))
+    }
+}
+
+impl Strategy for CustomStrategy {
+    fn get_move(
+        &self,
+        state: State<PlayerInfo>,
+        start: Position,
+        goal_tile: Position,
+    ) -> PlayerAction {
+        self.get_bounded_move(state, start, goal_tile, usize::MAX)
+    }
+
+    fn get_bounded_move(
+        &self,
+        state: State<PlayerInfo>,
+        start: Position,
+        goal_tile: Position,
+        max_candidates: usize,
+    ) -> PlayerAction {
+        find_move_to_reach(&state, start, goal_tile).or_else(|| {
+            self.get_alt_goals(goal_tile, &state)
+                .into_iter()
+                .take(max_candidates)
+                .find_map(|goal| find_move_to_reach(&state, start, goal))
+        })
+    }
+}
+
+impl<S: Strategy + ?Sized> Strategy for Box<S> {
+    fn get_move(
+        &self,
+        state: State<PlayerInfo>,
+        start: Position,
+        goal_tile: Position,
+    ) -> PlayerAction {
+        (**self).get_move(state, start, goal_tile)
+    }
+
+    fn get_bounded_move(
+        &self,
+        state: State<PlayerInfo>,
+        start: Position,
+        goal_tile: Position,
+        max_candidates: usize,
+    ) -> PlayerAction {
+        (**self).get_bounded_move(state, start, goal_tile, max_candidates)
+    }
+}
+
+/// Decorates a `Strategy` so that it evaluates at most `max_candidates` alternative destinations
+/// before giving up and passing. This bounds the work done for a single move on large boards.
+#[derive(Debug, Clone, Copy)]
+pub struct BoundedStrategy<S: Strategy> {
+    pub strategy: S,
+    pub max_candidates: usize,
+}
+
+impl<S: Strategy> BoundedStrategy<S> {
+    pub fn new(strategy: S, max_candidates: usize) -> Self {
+        Self {
+            strategy,
+            max_candidates,
+        }
+    }
+}
+
+impl<S: Strategy> Strategy for BoundedStrategy<S> {
+    fn get_move(
+        &self,
+        state: State<PlayerInfo>,
+        start: Position,
+        goal_tile: Position,
+    ) -> PlayerAction {
+        self.strategy
+            .get_bounded_move(state, start, goal_tile, self.max_candidates)
+    }
+}
+
+/// Decorates a `Strategy` so that it avoids ending its turn on another player's home.
+///
+/// If the move chosen by `strategy` ends on an opponent's home, the same slide and rotation are
+/// kept, but the destination is swapped for the closest one to the goal tile that is not an
+/// opponent's home and is no farther from the goal tile. If there is no such destination, the
+/// move is left as is.
+#[derive(Debug, Clone, Copy)]
+pub struct AvoidOpponentHomesStrategy<S: Strategy> {
+    pub strategy: S,
+}
+
+impl<S: Strategy> AvoidOpponentHomesStrategy<S> {
+    pub fn new(strategy: S) -> Self {
+        Self { strategy }
+    }
+
+    /// Replaces the destination of `action` if it is the home of a player other than the active
+    /// player in `state`.
+    fn avoid_opponent_homes(
+        &self,
+        state: &State<PlayerInfo>,
+        start: Position,
+        goal_tile: Position,
+        action: PlayerAction,
+    ) -> PlayerAction {
+        let opponent_homes = state
+            .player_info
+            .iter()
+            .skip(1)
+            .map(|pi| pi.home)
+            .collect::<Vec<_>>();
+        let PlayerMove {
+            slide,
+            rotations,
+            destination,
+        } = action?;
+        if !opponent_homes.contains(&destination) {
+            return action;
+        }
+
+        let max_distance = squared_euclidian_distance(&destination, &goal_tile);
+        sorted_alt_goals(state, |p1, p2| {
+            squared_euclidian_distance(p1, &goal_tile)
+                .cmp(&squared_euclidian_distance(p2, &goal_tile))
+                .then(row_col_order(p1, p2))
+        })
+        .into_iter()
+        .take_while(|pos| squared_euclidian_distance(pos, &goal_tile) <= max_distance)
+        .filter(|pos| !opponent_homes.contains(pos))
+        .find(|&pos| state.reachable_after_move(slide, rotations, pos, start))
+        .map(|destination| PlayerMove {
+            slide,
+            rotations,
+            destination,
+        })
+        .or(action)
+    }
+}
+
+impl<S: Strategy> Strategy for AvoidOpponentHomesStrategy<S> {
+    fn get_move(
+        &self,
+        state: State<PlayerInfo>,
+        start: Position,
+        goal_tile: Position,
+    ) -> PlayerAction {
+        let action = self.strategy.get_move(state.clone(), start, goal_tile);
+        self.avoid_opponent_homes(&state, start, goal_tile, action)
+    }
+
+    fn get_bounded_move(
+        &self,
+        state: State<PlayerInfo>,
+        start: Position,
+        goal_tile: Position,
+        max_candidates: usize,
+    ) -> PlayerAction {
+        let action =
+            self.strategy
+                .get_bounded_move(state.clone(), start, goal_tile, max_candidates);
+        self.avoid_opponent_homes(&state, start, goal_tile, action)
+    }
 }
 
 #[cfg(test)]
@@ -349,7 +600,7 @@ mod strategy_tests {
             connector: vertical,
             gems: (Gem::Zircon, Gem::Zoisite).into(),
         };
-        state.previous_slide = state.board.new_slide(2, East);
+        state.set_previous_slide(state.board.new_slide(2, East));
         state.board.grid[(0, 1)].connector = horizontal;
         state.board.grid[(0, 3)].connector = horizontal;
         state.board.grid[(1, 1)].connector = horizontal;
@@ -373,6 +624,55 @@ mod strategy_tests {
         assert_eq!(riemann.get_move(state, (0, 2), (3, 1)), None);
     }
 
+    #[test]
+    fn test_bounded_strategy() {
+        let mut state: State<PlayerInfo> = State {
+            player_info: vec![PlayerInfo {
+                current: (1, 1),
+                home: (1, 1),
+                color: ColorName::Red.into(),
+            }]
+            .into(),
+            ..Default::default()
+        };
+        let horizontal = ConnectorShape::Path(PathOrientation::Horizontal);
+        state.board.grid = Grid::from([[(); 7]; 7].map(|list| {
+            list.map(|_| Tile {
+                connector: horizontal,
+                gems: (Gem::Zircon, Gem::Zoisite).into(),
+            })
+        }));
+        state.board.spare.connector = horizontal;
+        // Board is:
+        //   0123456
+        // 0 ───────
+        // 1 ───────
+        // 2 ───────
+        // 3 ───────
+        // 4 ───────
+        // 5 ───────
+        // 6 ───────
+        //
+        // extra = ─
+        //
+        // row 1 can never be slid, so the player can never leave it. Riemann checks every tile in
+        // row 0 before finding (0, 1) as an alternative goal.
+        let riemann = NaiveStrategy::Riemann;
+        let unbounded = riemann.get_move(state.clone(), (1, 1), (1, 5));
+        assert_eq!(unbounded.map(|mv| mv.destination), Some((0, 1)));
+
+        let bounded = BoundedStrategy::new(riemann, 5);
+        assert_eq!(bounded.get_move(state.clone(), (1, 1), (1, 5)), None);
+
+        // the bound does not stop the strategy from going straight to a reachable goal
+        assert_eq!(
+            bounded
+                .get_move(state, (1, 1), (4, 1))
+                .map(|mv| mv.destination),
+            Some((4, 1))
+        );
+    }
+
     #[test]
     fn test_get_move_reimann() {
         let state = State {
@@ -628,6 +928,150 @@ mod strategy_tests {
         assert_eq!(euclid_alt_goals[max_cells - 1], (6, 6));
     }
 
+    #[test]
+    fn test_get_alt_goals_manhattan() {
+        let state = State::<PlayerInfo>::default();
+        let manhattan_alt_goals = NaiveStrategy::Manhattan.get_alt_goals((1, 1), &state);
+        let max_cells = state.board.num_rows() * state.board.num_cols();
+        assert_eq!(manhattan_alt_goals.len(), max_cells);
+        assert_eq!(manhattan_alt_goals[0], (1, 1));
+        assert_eq!(manhattan_alt_goals[1], (1, 0));
+        assert_eq!(manhattan_alt_goals[2], (0, 1));
+        assert_eq!(manhattan_alt_goals[3], (2, 1));
+        assert_eq!(manhattan_alt_goals[4], (1, 2));
+        assert_eq!(manhattan_alt_goals[5], (0, 0));
+        assert_eq!(manhattan_alt_goals[6], (2, 0));
+        assert_eq!(manhattan_alt_goals[7], (3, 1));
+        assert_eq!(manhattan_alt_goals[max_cells - 2], (5, 6));
+        assert_eq!(manhattan_alt_goals[max_cells - 1], (6, 6));
+    }
+
+    #[test]
+    fn test_custom_strategy() {
+        let state = State::<PlayerInfo>::default();
+        let manhattan = CustomStrategy::new(|p1, p2, goal_tile| {
+            let manhattan1 = p1.0.abs_diff(goal_tile.0) + p1.1.abs_diff(goal_tile.1);
+            let manhattan2 = p2.0.abs_diff(goal_tile.0) + p2.1.abs_diff(goal_tile.1);
+            manhattan1.cmp(&manhattan2).then(row_col_order(p1, p2))
+        });
+
+        // (1, 3) can't be reached from (0, 0), and after sliding the top row East, (1, 1) and
+        // (2, 2) are both 2 steps away from it. Euclid picks (2, 2), which is closer by its metric.
+        let custom_move = manhattan.get_move(state.clone(), (0, 0), (1, 3));
+        assert_eq!(
+            custom_move,
+            Some(PlayerMove {
+                slide: state.board.new_slide(0, East).unwrap(),
+                rotations: 0,
+                destination: (1, 1),
+            })
+        );
+        assert_eq!(
+            NaiveStrategy::Euclid.get_move(state.clone(), (0, 0), (1, 3)),
+            Some(PlayerMove {
+                slide: state.board.new_slide(0, East).unwrap(),
+                rotations: 0,
+                destination: (2, 2),
+            })
+        );
+
+        for (start, goal) in [((0, 0), (5, 2)), ((0, 1), (1, 5)), ((0, 1), (2, 4))] {
+            assert_eq!(
+                manhattan.get_move(state.clone(), start, goal),
+                NaiveStrategy::Manhattan.get_move(state.clone(), start, goal)
+            );
+            assert_ne!(
+                manhattan.get_move(state.clone(), start, goal),
+                NaiveStrategy::Euclid.get_move(state.clone(), start, goal)
+            );
+        }
+    }
+
+    #[test]
+    fn test_avoid_opponent_homes_strategy() {
+        let mut state = State::<PlayerInfo>::default();
+        state.add_player(PlayerInfo {
+            current: (0, 0),
+            home: (5, 5),
+            color: ColorName::Red.into(),
+        });
+        let slide = state.board.new_slide(0, East).unwrap();
+
+        // As in `test_custom_strategy`, Manhattan picks (1, 1) when it cannot reach (1, 3) from
+        // (0, 0), while (2, 2) is just as close
+        let avoid = AvoidOpponentHomesStrategy::new(NaiveStrategy::Manhattan);
+        assert_eq!(
+            avoid.get_move(state.clone(), (0, 0), (1, 3)),
+            Some(PlayerMove {
+                slide,
+                rotations: 0,
+                destination: (1, 1),
+            })
+        );
+
+        state.add_player(PlayerInfo {
+            current: (3, 3),
+            home: (1, 1),
+            color: ColorName::Blue.into(),
+        });
+        assert_eq!(
+            NaiveStrategy::Manhattan.get_move(state.clone(), (0, 0), (1, 3)),
+            Some(PlayerMove {
+                slide,
+                rotations: 0,
+                destination: (1, 1),
+            })
+        );
+        assert_eq!(
+            avoid.get_move(state.clone(), (0, 0), (1, 3)),
+            Some(PlayerMove {
+                slide,
+                rotations: 0,
+                destination: (2, 2),
+            })
+        );
+
+        // the active player's own home is not avoided
+        state.next_player();
+        assert_eq!(
+            avoid.get_move(state, (0, 0), (1, 3)),
+            Some(PlayerMove {
+                slide,
+                rotations: 0,
+                destination: (1, 1),
+            })
+        );
+    }
+
+    #[test]
+    fn test_strategy_from_name() {
+        let state = State {
+            player_info: vec![PlayerInfo {
+                current: (0, 0),
+                home: (0, 0),
+                color: ColorName::Red.into(),
+            }]
+            .into(),
+            ..Default::default()
+        };
+
+        for (name, strategy) in [
+            ("Euclid", NaiveStrategy::Euclid),
+            ("Riemann", NaiveStrategy::Riemann),
+            ("Manhattan", NaiveStrategy::Manhattan),
+        ] {
+            let from_name = strategy_from_name(name).unwrap();
+            assert_eq!(
+                from_name.get_move(state.clone(), (0, 0), (1, 1)),
+                strategy.get_move(state.clone(), (0, 0), (1, 1))
+            );
+            assert!(from_name.get_move(state.clone(), (0, 0), (1, 1)).is_some());
+        }
+
+        assert!(strategy_from_name("Dijkstra").is_none());
+        assert!(strategy_from_name("euclid").is_none());
+    }
+
     #[test]
     fn test_find_move_to_reach() {
         let state = State {
@@ -1,4 +1,4 @@
-use crate::referee::GameResult;
+use crate::referee::{Config, GameResult, GoalOrder, RefereeSnapshot};
 use common::{
     board::Board,
     color::Color,
@@ -9,13 +9,18 @@ use common::{
     },
     state::{FullPlayerInfo, PrivatePlayerInfo, PublicPlayerInfo, State},
 };
-use players::{bad_player::BadFM, player::PlayerApi, strategy::NaiveStrategy};
+use players::{
+    bad_player::BadFM,
+    json::StrategyConfig,
+    player::PlayerApi,
+    strategy::{strategy_from_name, Strategy},
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, PartialEq, Eq)]
 pub struct PS(Name, JsonStrategy);
 
-impl From<PS> for (Name, NaiveStrategy) {
+impl From<PS> for (Name, Box<dyn Strategy + Send>) {
     fn from(ps: PS) -> Self {
         (ps.0, ps.1.into())
     }
@@ -25,8 +30,27 @@ impl From<PS> for (Name, NaiveStrategy) {
 fn ps_parse_test() {
     assert_eq!(
         serde_json::from_str::<PS>("[\"bob\", \"Riemann\"]").unwrap(),
-        PS(Name::from_static("bob"), JsonStrategy::Riemann)
+        PS(
+            Name::from_static("bob"),
+            JsonStrategy(StrategyConfig {
+                name: String::from("Riemann"),
+                max_candidates: None,
+            })
+        )
+    );
+    assert_eq!(
+        serde_json::from_str::<PS>("[\"bob\", { \"name\": \"Euclid\", \"max_candidates\": 3 }]")
+            .unwrap(),
+        PS(
+            Name::from_static("bob"),
+            JsonStrategy(StrategyConfig {
+                name: String::from("Euclid"),
+                max_candidates: Some(3),
+            })
+        )
     );
+    assert!(serde_json::from_str::<PS>("[\"bob\", \"Dijkstra\"]").is_err());
+    assert!(serde_json::from_str::<PlayerSpec>("[\"bob\", { \"name\": \"Dijkstra\" }]").is_err());
 }
 
 #[derive(Debug, Deserialize, PartialEq, Eq)]
@@ -43,33 +67,44 @@ pub enum PlayerSpec {
     BadPS2(BadPS2),
 }
 
-impl From<BadPS> for (Name, NaiveStrategy, BadFM) {
+impl From<BadPS> for (Name, Box<dyn Strategy + Send>, BadFM) {
     fn from(bad_ps: BadPS) -> Self {
         (bad_ps.0, bad_ps.1.into(), bad_ps.2)
     }
 }
 
-impl From<BadPS2> for (Name, NaiveStrategy, BadFM, u64) {
+impl From<BadPS2> for (Name, Box<dyn Strategy + Send>, BadFM, u64) {
     fn from(bad_ps2: BadPS2) -> Self {
         (bad_ps2.0, bad_ps2.1.into(), bad_ps2.2, bad_ps2.3)
     }
 }
 
+/// A `StrategyConfig` naming a `Strategy` that exists, so players can always be made from it.
 #[derive(Debug, Deserialize, PartialEq, Eq)]
-pub enum JsonStrategy {
-    Riemann,
-    Euclid,
+#[serde(try_from = "StrategyConfig")]
+pub struct JsonStrategy(StrategyConfig);
+
+impl TryFrom<StrategyConfig> for JsonStrategy {
+    type Error = JsonError;
+
+    fn try_from(config: StrategyConfig) -> Result<Self, Self::Error> {
+        match strategy_from_name(&config.name) {
+            Some(_) => Ok(JsonStrategy(config)),
+            None => Err(JsonError::UnknownStrategy(config.name)),
+        }
+    }
 }
 
-impl From<JsonStrategy> for NaiveStrategy {
+impl From<JsonStrategy> for Box<dyn Strategy + Send> {
     fn from(jss: JsonStrategy) -> Self {
-        match jss {
-            JsonStrategy::Riemann => NaiveStrategy::Riemann,
-            JsonStrategy::Euclid => NaiveStrategy::Euclid,
-        }
+        jss.0
+            .into_strategy()
+            .expect("JsonStrategy only holds the names of existing strategies")
     }
 }
 
+/// The referee's view of a game: a `JsonState` plus every player's goal and the remaining goals.
+/// Unknown fields are ignored.
 #[allow(dead_code)]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct JsonRefereeState {
@@ -87,15 +122,7 @@ fn valid_positions(
     unique_error: impl FnOnce(Vec<Color>) -> JsonError,
 ) -> Result<(), JsonError> {
     let valid = valid.as_ref();
-    let invalid = player_info
-        .iter()
-        .filter(|(_, position)| !valid.contains(position))
-        .map(|(color, _)| color)
-        .collect::<Vec<_>>();
-    if !invalid.is_empty() {
-        return Err(unique_error(invalid.into_iter().cloned().collect()));
-    }
-
+    // check bounds first, so a position past the edge is not mistaken for the spare tile
     player_info
         .iter()
         .fold(Ok(()), |acc: Result<(), JsonError>, (_, position)| {
@@ -105,6 +132,15 @@ fn valid_positions(
                 .ok_or_else(|| JsonError::PositionOutOfBounds(vec![*position]))?;
             acc
         })?;
+
+    let invalid = player_info
+        .iter()
+        .filter(|(_, position)| !valid.contains(position))
+        .map(|(color, _)| color)
+        .collect::<Vec<_>>();
+    if !invalid.is_empty() {
+        return Err(unique_error(invalid.into_iter().cloned().collect()));
+    }
     Ok(())
 }
 
@@ -117,6 +153,15 @@ where
     fn try_from(jstate: JsonRefereeState) -> Result<Self, Self::Error> {
         let board: Board = (jstate.board, jstate.spare).try_into()?;
 
+        // players without a `goto` are going home, so only explicit goals are checked against the
+        // remaining goals
+        let assigned_goals: Vec<Position> = jstate
+            .plmt
+            .iter()
+            .filter_map(|jrp| jrp.goto.as_ref())
+            .map(|goto| (goto.column.0, goto.row.0))
+            .collect();
+
         let player_info: Vec<PI> = jstate
             .plmt
             .into_iter()
@@ -189,6 +234,10 @@ where
             JsonError::PlayerGoalMoveableTile,
         )?;
 
+        if assigned_goals.iter().any(|goal| rem_goals.contains(goal)) {
+            return Err(JsonError::DuplicateAssignedGoals);
+        }
+
         let previous_slide = jstate.last.into();
         if let Some(slide) = previous_slide {
             if !board.valid_slide(slide) {
@@ -220,11 +269,153 @@ impl From<State<FullPlayerInfo>> for JsonRefereeState {
     }
 }
 
+/// The Json representation of a `RefereeSnapshot`. The remaining goals are stored in the `goals`
+/// of `state`, and `goals_reached` holds the number of goals each player in `state` has reached,
+/// in turn order. `goals_history` holds the goals each player collected, in the same order, and
+/// may be left out.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct JsonRefereeSnapshot {
+    state: JsonRefereeState,
+    goals_reached: Vec<u64>,
+    #[serde(default)]
+    goals_history: Vec<Vec<Coordinate>>,
+    config: JsonConfig,
+}
+
+impl From<RefereeSnapshot> for JsonRefereeSnapshot {
+    fn from(snapshot: RefereeSnapshot) -> Self {
+        let goals_reached = snapshot
+            .state
+            .player_info
+            .iter()
+            .map(|pi| pi.get_goals_reached())
+            .collect();
+        let goals_history = snapshot
+            .state
+            .player_info
+            .iter()
+            .map(|pi| pi.goals_history().iter().map(|&goal| goal.into()).collect())
+            .collect();
+        let mut state: JsonRefereeState = snapshot.state.into();
+        state.goals = Some(
+            snapshot
+                .remaining_goals
+                .into_iter()
+                .map(|goal| goal.into())
+                .collect(),
+        );
+        JsonRefereeSnapshot {
+            state,
+            goals_reached,
+            goals_history,
+            config: snapshot.config.into(),
+        }
+    }
+}
+
+impl TryFrom<JsonRefereeSnapshot> for RefereeSnapshot {
+    type Error = JsonError;
+
+    fn try_from(jsnapshot: JsonRefereeSnapshot) -> Result<Self, Self::Error> {
+        let (mut state, remaining_goals): (State<FullPlayerInfo>, Vec<Position>) =
+            jsnapshot.state.try_into()?;
+        if jsnapshot.goals_reached.len() != state.player_count() {
+            return Err(JsonError::GoalsReachedMismatch(
+                state.player_count(),
+                jsnapshot.goals_reached.len(),
+            ));
+        }
+        let mut goals_history = jsnapshot.goals_history;
+        if goals_history.is_empty() {
+            goals_history.resize_with(state.player_count(), Vec::new);
+        } else if goals_history.len() != state.player_count() {
+            return Err(JsonError::GoalsReachedMismatch(
+                state.player_count(),
+                goals_history.len(),
+            ));
+        }
+        for ((pi, goals_reached), history) in state
+            .player_info
+            .iter_mut()
+            .zip(jsnapshot.goals_reached)
+            .zip(goals_history)
+        {
+            // goals reached before their history was kept are only counted
+            let uncollected = goals_reached.saturating_sub(history.len() as u64);
+            (0..uncollected).for_each(|_| pi.inc_goals_reached());
+            history
+                .into_iter()
+                .for_each(|goal| pi.collect_goal(goal.into()));
+        }
+
+        Ok(RefereeSnapshot {
+            state,
+            remaining_goals: remaining_goals.into(),
+            config: jsnapshot.config.try_into()?,
+        })
+    }
+}
+
+/// The Json representation of a `referee::Config`
+#[derive(Debug, Deserialize, Serialize)]
+pub struct JsonConfig {
+    multiple_goals: bool,
+    goal_order: GoalOrder,
+    player_shuffle_seed: Option<u64>,
+    board: Option<(JsonBoard, JsonTile)>,
+    time_turns: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_players: Option<usize>,
+    #[serde(default)]
+    broadcast_on_change: bool,
+    #[serde(default = "default_no_goal_penalty")]
+    no_goal_penalty: bool,
+}
+
+/// Configs written before `no_goal_penalty` existed keep the scoring they were written for.
+fn default_no_goal_penalty() -> bool {
+    true
+}
+
+impl From<Config> for JsonConfig {
+    fn from(config: Config) -> Self {
+        JsonConfig {
+            multiple_goals: config.multiple_goals,
+            goal_order: config.goal_order,
+            player_shuffle_seed: config.player_shuffle_seed,
+            board: config.board.map(Into::into),
+            time_turns: config.time_turns,
+            max_players: config.max_players,
+            broadcast_on_change: config.broadcast_on_change,
+            no_goal_penalty: config.no_goal_penalty,
+        }
+    }
+}
+
+impl TryFrom<JsonConfig> for Config {
+    type Error = JsonError;
+
+    fn try_from(jconfig: JsonConfig) -> Result<Self, Self::Error> {
+        Ok(Config {
+            multiple_goals: jconfig.multiple_goals,
+            goal_order: jconfig.goal_order,
+            player_shuffle_seed: jconfig.player_shuffle_seed,
+            board: jconfig.board.map(Board::try_from).transpose()?,
+            time_turns: jconfig.time_turns,
+            max_players: jconfig.max_players,
+            broadcast_on_change: jconfig.broadcast_on_change,
+            no_goal_penalty: jconfig.no_goal_penalty,
+        })
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct JsonRefereePlayer {
     current: Coordinate,
     home: Coordinate,
-    goto: Coordinate,
+    /// The player's goal. Players without a known goal are treated as going home.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    goto: Option<Coordinate>,
     color: JsonColor,
 }
 
@@ -232,10 +423,11 @@ impl TryFrom<JsonRefereePlayer> for FullPlayerInfo {
     type Error = JsonError;
 
     fn try_from(jrp: JsonRefereePlayer) -> Result<Self, Self::Error> {
+        let home = jrp.home.into();
         Ok(Self::new(
-            jrp.home.into(),
+            home,
             jrp.current.into(),
-            jrp.goto.into(),
+            jrp.goto.map_or(home, Into::into),
             jrp.color.try_into()?,
         ))
     }
@@ -246,7 +438,7 @@ impl From<FullPlayerInfo> for JsonRefereePlayer {
         JsonRefereePlayer {
             current: pi.position().into(),
             home: pi.home().into(),
-            goto: pi.goal.into(),
+            goto: Some(pi.goal.into()),
             color: pi.color().into(),
         }
     }
@@ -263,3 +455,93 @@ impl From<GameResult> for JsonGameResult {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use common::color::ColorName;
+
+    use super::*;
+
+    #[test]
+    fn test_referee_player_missing_goto() {
+        let state = State {
+            player_info: vec![
+                FullPlayerInfo::new((1, 1), (0, 0), (5, 5), ColorName::Red.into()),
+                FullPlayerInfo::new((3, 3), (2, 2), (1, 5), ColorName::Blue.into()),
+            ]
+            .into(),
+            ..Default::default()
+        };
+        let mut json = serde_json::to_value(JsonRefereeState::from(state.clone())).unwrap();
+        json["plmt"][1].as_object_mut().unwrap().remove("goto");
+
+        let jstate: JsonRefereeState = serde_json::from_value(json).unwrap();
+        let (parsed, goals): (State<FullPlayerInfo>, Vec<Position>) = jstate.try_into().unwrap();
+        assert!(goals.is_empty());
+        assert_eq!(parsed.player_info[0], state.player_info[0]);
+        assert_eq!(parsed.player_info[1].goal(), (3, 3));
+        assert_eq!(parsed.player_info[1].home(), (3, 3));
+        assert_eq!(parsed.player_info[1].position(), (2, 2));
+    }
+
+    #[test]
+    fn test_referee_state_duplicate_assigned_goals() {
+        let state = State {
+            player_info: vec![
+                FullPlayerInfo::new((1, 1), (0, 0), (5, 5), ColorName::Red.into()),
+                FullPlayerInfo::new((3, 3), (2, 2), (1, 5), ColorName::Blue.into()),
+            ]
+            .into(),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(JsonRefereeState::from(state)).unwrap();
+        let with_goals = |goals: serde_json::Value| {
+            let mut json = json.clone();
+            json.as_object_mut().unwrap().insert("goals".into(), goals);
+            serde_json::from_value::<JsonRefereeState>(json).unwrap()
+        };
+
+        let disjoint = with_goals(serde_json::json!([
+            {"row#": 1, "column#": 3},
+            {"row#": 3, "column#": 5}
+        ]));
+        let (_, goals): (State<FullPlayerInfo>, Vec<Position>) = disjoint.try_into().unwrap();
+        assert_eq!(goals, vec![(3, 1), (5, 3)]);
+
+        // blue's goal is (1, 5)
+        let duplicate = with_goals(serde_json::json!([
+            {"row#": 1, "column#": 3},
+            {"row#": 5, "column#": 1}
+        ]));
+        let parsed: Result<(State<FullPlayerInfo>, Vec<Position>), _> = duplicate.try_into();
+        assert!(matches!(parsed, Err(JsonError::DuplicateAssignedGoals)));
+    }
+
+    #[test]
+    fn test_referee_state_ignores_unknown_fields() {
+        let state = State {
+            player_info: vec![FullPlayerInfo::new(
+                (1, 1),
+                (0, 0),
+                (5, 5),
+                ColorName::Red.into(),
+            )]
+            .into(),
+            ..Default::default()
+        };
+        let expected = serde_json::to_value(JsonRefereeState::from(state)).unwrap();
+
+        let mut json = expected.clone();
+        json["plmt"][0]
+            .as_object_mut()
+            .unwrap()
+            .insert("notes".into(), "joined late".into());
+
+        let jstate: JsonRefereeState = serde_json::from_value(json).unwrap();
+        let (parsed, _): (State<FullPlayerInfo>, Vec<Position>) = jstate.try_into().unwrap();
+        assert_eq!(
+            serde_json::to_value(JsonRefereeState::from(parsed)).unwrap(),
+            expected
+        );
+    }
+}
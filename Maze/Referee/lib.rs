@@ -3,7 +3,7 @@
 //! # Library Layout
 //! ## Referee
 //! Contains an implementation of the [`referee::Referee`] which is an arbiter of a Maze`.`com
-//! game.
+//! game. A paused game can be saved as a [`referee::RefereeSnapshot`] and resumed later.
 //!
 //! ## Player
 //! Contains a wrapper around a `PlayerApi` and a `PrivatePlayerInfo` for convenience and coupling
@@ -17,7 +17,8 @@
 //! ### Json
 //! Contains the data definitions for integration tests of the [`referee::Referee`].
 
-/// Contains the Observer trait and a GUI implementation.
+/// Contains the Observer trait, a GUI implementation, and an implementation that writes states as
+/// JSON.
 pub mod observer;
 
 /// Contains all the data types and functionalities used for interfacing with Players and handling timeouts
@@ -1,18 +1,20 @@
 use std::{
-    collections::VecDeque,
+    collections::{HashSet, VecDeque},
     fs::File,
+    io::{Read, Write},
     sync::{Arc, Mutex},
 };
 
 use common::{
-    board::Slide,
+    board::{Board, Slide},
     color::Color,
     gem::GEM_IMGS,
-    grid::Grid as CGrid,
-    state::{FullPlayerInfo, PublicPlayerInfo, State},
+    grid::{Grid as CGrid, Position},
+    json::json_stream,
+    state::{FullPlayerInfo, PrivatePlayerInfo, PublicPlayerInfo, State},
     tile::{CompassDirection, ConnectorShape, PathOrientation, Tile},
 };
-use egui::{Align, Color32, Grid, Image, Layout, RichText, Ui, Vec2};
+use egui::{Align, Color32, Grid, Image, Layout, RichText, Stroke, Ui, Vec2};
 use egui_extras::RetainedImage;
 
 use lazy_static::lazy_static;
@@ -79,15 +81,23 @@ fn to_color_32(color: &Color) -> Color32 {
 // size, in pixels, of a cell
 const CELL_SIZE: f32 = 30.0;
 const CELL_SIZE_2D: Vec2 = Vec2::new(CELL_SIZE, CELL_SIZE);
+// translucent overlay drawn over tiles the active player can reach
+const REACHABLE_TINT: Color32 = Color32::from_rgba_premultiplied(60, 60, 0, 60);
+/// Width of the border drawn around a tile that is a player's goal
+const GOAL_BORDER_WIDTH: f32 = 4.0;
 
 /// struct for holding information about a Tile that's being rendered
 /// `home_colors` is a vector of all the colors of homes on this tile
 /// `player_colors` is a vector of all the colors of players on this tile
+/// `goal_color` is the color of the player whose goal is this tile, if any
+/// `reachable` is whether this tile should be tinted as reachable by the active player
 #[derive(Debug, Clone)]
 struct TileWidget {
     tile: Tile,
     home_color: Option<Color>,
+    goal_color: Option<Color>,
     player_colors: Vec<Color>,
+    reachable: bool,
 }
 
 impl TileWidget {
@@ -203,7 +213,7 @@ fn render_tile(ui: &mut egui::Ui, widget: TileWidget, id: &str) {
     let (gem1_img, gem2_img) = widget.gem_images(ui);
 
     // creates main grid for the tile
-    Grid::new(format!("{} main", id))
+    let tile_rect = Grid::new(format!("{} main", id))
         .min_col_width(0.0)
         .spacing(Vec2::new(0.0, 0.0))
         .show(ui, |ui| {
@@ -220,12 +230,40 @@ fn render_tile(ui: &mut egui::Ui, widget: TileWidget, id: &str) {
             widget.render_players(ui, id);
             widget.south_path().show(ui);
             ui.add_sized(CELL_SIZE_2D, gem2_img);
-        });
+        })
+        .response
+        .rect;
+
+    if widget.reachable {
+        ui.painter().rect_filled(tile_rect, 0.0, REACHABLE_TINT);
+    }
+    if let Some(col) = &widget.goal_color {
+        ui.painter().rect_stroke(
+            tile_rect.shrink(GOAL_BORDER_WIDTH / 2.0),
+            0.0,
+            Stroke::new(GOAL_BORDER_WIDTH, to_color_32(col)),
+        );
+    }
+}
+
+/// Returns the set of `Position`s the active player in `state` can reach, or an empty set if there
+/// are no players in `state`.
+fn reachable_positions(state: &State<FullPlayerInfo>) -> HashSet<Position> {
+    if state.is_empty() {
+        HashSet::new()
+    } else {
+        state.reachable_by_player().into_iter().collect()
+    }
 }
 
 /// Returns a `common::Grid<TileWidget>` containing all the `Tile` information in `state`.
-/// This includes the home and player locations, but not the goal locations
-fn widget_grid(state: &State<FullPlayerInfo>) -> CGrid<TileWidget> {
+/// This includes the home and player locations, and the goal locations if `show_goals` is `true`.
+/// If `show_reachable` is `true`, the tiles reachable by the active player are marked as such.
+fn widget_grid(
+    state: &State<FullPlayerInfo>,
+    show_reachable: bool,
+    show_goals: bool,
+) -> CGrid<TileWidget> {
     let mut tiles: CGrid<TileWidget> = state
         .board
         .grid
@@ -236,6 +274,8 @@ fn widget_grid(state: &State<FullPlayerInfo>) -> CGrid<TileWidget> {
                     tile: tile.clone(),
                     player_colors: vec![],
                     home_color: None,
+                    goal_color: None,
+                    reachable: false,
                 })
                 .collect::<Box<[TileWidget]>>()
         })
@@ -246,14 +286,28 @@ fn widget_grid(state: &State<FullPlayerInfo>) -> CGrid<TileWidget> {
     state.player_info.iter().for_each(|pi| {
         tiles[pi.position()].player_colors.push(pi.color());
         tiles[pi.home()].home_color = Some(pi.color());
+        if show_goals {
+            tiles[pi.goal()].goal_color = Some(pi.color());
+        }
     });
 
+    if show_reachable {
+        reachable_positions(state)
+            .into_iter()
+            .for_each(|pos| tiles[pos].reachable = true);
+    }
+
     tiles
 }
 
 // Render's the `board` inside of a state
-fn render_board(ui: &mut egui::Ui, state: &State<FullPlayerInfo>) {
-    let tiles: CGrid<TileWidget> = widget_grid(state);
+fn render_board(
+    ui: &mut egui::Ui,
+    state: &State<FullPlayerInfo>,
+    show_reachable: bool,
+    show_goals: bool,
+) {
+    let tiles: CGrid<TileWidget> = widget_grid(state, show_reachable, show_goals);
 
     // create board grid
     Grid::new("board_grid")
@@ -300,12 +354,15 @@ fn render_state_info(ui: &mut egui::Ui, state: &State<FullPlayerInfo>) {
         tile: state.board.spare.clone(),
         player_colors: vec![],
         home_color: None,
+        goal_color: None,
+        reachable: false,
     };
 
     let spare_text = RichText::new("Spare Tile:").heading().strong();
     let last_text = RichText::new("Last Slide:").heading().strong();
     let curr_player_text = RichText::new("Current Player").heading().strong();
     let no_players_text = RichText::new("No Players in Game!").heading().strong();
+    let players_text = RichText::new("Players").heading().strong();
 
     ui.vertical_centered(|ui| {
         ui.add_space(CELL_SIZE * 2.0);
@@ -317,23 +374,58 @@ fn render_state_info(ui: &mut egui::Ui, state: &State<FullPlayerInfo>) {
         render_slide(ui, state);
 
         ui.add_space(CELL_SIZE * 2.0);
-        if state.player_info.is_empty() {
+        if state.is_empty() {
             ui.label(no_players_text);
         } else {
             ui.label(curr_player_text);
-            let curr_pl = player_image_with_color(ui, &state.player_info[0].color(), CELL_SIZE_2D);
+            let color = state.player_info[0].color();
+            let curr_pl = player_image_with_color(ui, &color, CELL_SIZE_2D);
             ui.add_sized(CELL_SIZE_2D * 0.5, curr_pl);
+            ui.label(color_label(&color));
+
+            ui.add_space(CELL_SIZE * 2.0);
+            ui.label(players_text);
+            legend_colors(state).iter().for_each(|color| {
+                ui.label(color_label(color));
+            });
         }
     });
 }
 
-/// Render `state` onto the `ui`
-fn render_state(ui: &mut egui::Ui, state: &State<FullPlayerInfo>) {
+/// Returns the name of `color` as text drawn on top of `color`
+fn color_label(color: &Color) -> RichText {
+    let (r, g, b) = color.best_text_color();
+    RichText::new(&color.name)
+        .strong()
+        .color(Color32::from_rgb(r, g, b))
+        .background_color(to_color_32(color))
+}
+
+/// Returns the colors of every player in `state` from darkest to lightest, so the legend of players
+/// stays in the same order as turns pass.
+fn legend_colors(state: &State<FullPlayerInfo>) -> Vec<Color> {
+    let mut colors = state
+        .player_info
+        .iter()
+        .map(|pi| pi.color())
+        .collect::<Vec<_>>();
+    colors.sort_by(Color::cmp_by_luminance);
+    colors
+}
+
+/// Render `state` onto the `ui`, tinting the tiles the active player can reach if
+/// `show_reachable` is `true` and outlining each player's goal if `show_goals` is `true`
+fn render_state(
+    ui: &mut egui::Ui,
+    state: &State<FullPlayerInfo>,
+    show_reachable: bool,
+    show_goals: bool,
+) {
     // create grid for the state
     Grid::new("state_grid")
         .spacing(Vec2::new(25.0, 0.0))
         .show(ui, |ui| {
-            render_board(ui, state);
+            render_board(ui, state, show_reachable, show_goals);
             ui.vertical(|ui| render_state_info(ui, state));
         });
 }
@@ -345,6 +437,24 @@ pub trait Observer {
 
     /// Indicates to the Observer that the game has ended and no more `State`s will be sent
     fn game_over(&mut self);
+
+    /// Called by the `Referee` after every round. Returning `false` stops the game before the
+    /// next round, ending it with `GameStatus::Stopped`.
+    fn should_continue(&mut self) -> bool {
+        true
+    }
+
+    /// Called by the `Referee` when the player with the given `color` reaches their last goal, and
+    /// their home becomes their goal. Reaching any goal before the last one does not call this.
+    fn goal_is_now_home(&mut self, _color: Color) {}
+
+    /// Called by the `Referee` before a game starts with the `Board`s proposed by its players, in
+    /// turn order. A player that failed to propose a `Board` has `None` in its place. Returning the
+    /// index of one of the `proposals` makes the `Referee` use that `Board`, and returning `None`
+    /// or the index of a missing proposal leaves the choice to the `Referee`.
+    fn choose_board(&mut self, _proposals: &[Option<Board>]) -> Option<usize> {
+        None
+    }
 }
 
 /// Contains all information needed for an ObserverGUI to render the game
@@ -356,6 +466,10 @@ pub struct ObserverGUI {
     states: Arc<Mutex<VecDeque<State<FullPlayerInfo>>>>,
     /// Flag indicating if the `Referee` has told the `ObserverGUI` the game has ended
     game_over: Arc<Mutex<bool>>,
+    /// Flag indicating if the tiles reachable by the active player should be tinted
+    show_reachable: bool,
+    /// Flag indicating if each player's goal tile should be outlined in their color
+    show_goals: bool,
 }
 
 impl Observer for ObserverGUI {
@@ -369,6 +483,90 @@ impl Observer for ObserverGUI {
     }
 }
 
+impl ObserverGUI {
+    /// Creates an `ObserverGUI` that replays a transcript of `JsonRefereeState`s, such as the ones
+    /// written by its "Save" button, read from `reader`.
+    ///
+    /// # Errors
+    /// This will error if any state in the transcript is not a valid `JsonRefereeState`
+    pub fn from_transcript(reader: impl Read) -> anyhow::Result<Self> {
+        let states: VecDeque<State<FullPlayerInfo>> = json_stream::<JsonRefereeState>(reader)
+            .map(|jstate| -> anyhow::Result<State<FullPlayerInfo>> {
+                let (state, _goals): (State<FullPlayerInfo>, Vec<Position>) = jstate?.try_into()?;
+                Ok(state)
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self {
+            states: Arc::new(Mutex::new(states)),
+            game_over: Arc::new(Mutex::new(true)),
+            ..Default::default()
+        })
+    }
+
+    /// Returns a copy of the most recent `State` this `ObserverGUI` has recieved, if any
+    pub fn latest_state(&self) -> Option<State<FullPlayerInfo>> {
+        self.states.lock().unwrap().back().cloned()
+    }
+
+    /// Has the `Referee` told this `ObserverGUI` the game has ended?
+    pub fn is_game_over(&self) -> bool {
+        *self.game_over.lock().unwrap()
+    }
+}
+
+/// The formats a `JsonObserver` can write the `State`s it recieves in
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ObserverFormat {
+    /// Every `State` is written as soon as it is recieved, one JSON object per line. The writer is
+    /// flushed after every line, so readers can consume the `State`s while the game is running.
+    #[default]
+    Lines,
+    /// All `State`s are written as a single JSON array once the game is over
+    Array,
+}
+
+/// An `Observer` that writes every `State` it recieves to `writer` as a `JsonRefereeState`, in
+/// the given `format`
+pub struct JsonObserver<W: Write> {
+    writer: W,
+    format: ObserverFormat,
+    /// The states waiting to be written when `format` is `ObserverFormat::Array`
+    pending: Vec<JsonRefereeState>,
+}
+
+impl<W: Write> JsonObserver<W> {
+    pub fn new(writer: W, format: ObserverFormat) -> Self {
+        Self {
+            writer,
+            format,
+            pending: vec![],
+        }
+    }
+}
+
+impl<W: Write> Observer for JsonObserver<W> {
+    fn recieve_state(&mut self, state: State<FullPlayerInfo>) {
+        let jstate: JsonRefereeState = state.into();
+        match self.format {
+            ObserverFormat::Lines => {
+                serde_json::to_writer(&mut self.writer, &jstate).expect("Writing to json failed!");
+                writeln!(self.writer).expect("Writing to json failed!");
+                self.writer.flush().expect("Writing to json failed!");
+            }
+            ObserverFormat::Array => self.pending.push(jstate),
+        }
+    }
+
+    fn game_over(&mut self) {
+        if self.format == ObserverFormat::Array {
+            serde_json::to_writer(&mut self.writer, &self.pending)
+                .expect("Writing to json failed!");
+            self.pending.clear();
+        }
+        self.writer.flush().expect("Writing to json failed!");
+    }
+}
+
 /// Writes the `JsonRefereeState` representation of `state` to a path the user chooses
 fn save_json_state(state: State<FullPlayerInfo>) {
     let path = std::env::current_dir().unwrap();
@@ -394,7 +592,7 @@ impl eframe::App for ObserverGUI {
 
             // if there are states to render, render the first state
             if !states.is_empty() {
-                render_state(ui, &states[0]);
+                render_state(ui, &states[0], self.show_reachable, self.show_goals);
             }
 
             // draw the buttons below the state
@@ -413,7 +611,250 @@ impl eframe::App for ObserverGUI {
                 if !states.is_empty() && ui.button("Save").clicked() {
                     save_json_state(states[0].clone());
                 }
+
+                ui.checkbox(&mut self.show_reachable, "Show reachable tiles");
+                ui.checkbox(&mut self.show_goals, "Show goals");
             });
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufWriter;
+
+    use common::{color::ColorName, json::Name};
+    use players::{player::LocalPlayer, strategy::NaiveStrategy};
+
+    use super::*;
+    use crate::referee::Referee;
+
+    /// A `Write`r whose contents can still be read after it is given to an `Observer`
+    #[derive(Debug, Default, Clone)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_json_observer_formats() {
+        let lines = SharedBuffer::default();
+        let array = SharedBuffer::default();
+        let mut referee = Referee::new(0);
+        referee.run_game(
+            vec![Box::new(LocalPlayer::new(
+                Name::from_static("bob"),
+                NaiveStrategy::Euclid,
+            ))],
+            vec![
+                Box::new(JsonObserver::new(lines.clone(), ObserverFormat::Lines)),
+                Box::new(JsonObserver::new(array.clone(), ObserverFormat::Array)),
+            ],
+        );
+
+        let lines = String::from_utf8(lines.0.lock().unwrap().clone()).unwrap();
+        let num_states = lines.lines().count();
+        assert!(num_states > 0);
+        assert!(lines
+            .lines()
+            .all(|line| serde_json::from_str::<JsonRefereeState>(line).is_ok()));
+
+        let array: Vec<JsonRefereeState> =
+            serde_json::from_slice(&array.0.lock().unwrap()).unwrap();
+        assert_eq!(array.len(), num_states);
+        for jstate in array {
+            let parsed: Result<(State<FullPlayerInfo>, Vec<Position>), _> = jstate.try_into();
+            assert!(parsed.is_ok());
+        }
+    }
+
+    #[test]
+    fn test_json_observer_flushes_lines() {
+        let buffer = SharedBuffer::default();
+        // nothing reaches `buffer` until the `BufWriter` is flushed
+        let mut observer = JsonObserver::new(
+            BufWriter::with_capacity(1 << 16, buffer.clone()),
+            ObserverFormat::Lines,
+        );
+
+        let mut state = State::<FullPlayerInfo>::default();
+        state.add_player(FullPlayerInfo::new(
+            (1, 1),
+            (1, 1),
+            (3, 3),
+            ColorName::Red.into(),
+        ));
+        for broadcasts in 1..=3 {
+            observer.recieve_state(state.clone());
+            state.player_info[0].set_position((broadcasts, 1));
+
+            let written = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+            assert!(written.ends_with('\n'));
+            let lines = written.lines().collect::<Vec<_>>();
+            assert_eq!(lines.len(), broadcasts);
+            for line in lines {
+                let jstate: JsonRefereeState = serde_json::from_str(line).unwrap();
+                let parsed: Result<(State<FullPlayerInfo>, Vec<Position>), _> = jstate.try_into();
+                assert!(parsed.is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_reachable_positions() {
+        let mut state = State::<FullPlayerInfo>::default();
+        assert!(reachable_positions(&state).is_empty());
+
+        state.add_player(FullPlayerInfo::new(
+            (1, 1),
+            (1, 1),
+            (3, 3),
+            ColorName::Red.into(),
+        ));
+        // Default Board<7> is:
+        //   0123456
+        // 0 ─│└┌┐┘┴
+        // 1 ├┬┤┼─│└
+        // 2 ┌┐┘┴├┬┤
+        // 3 ┼─│└┌┐┘
+        // 4 ┴├┬┤┼─│
+        // 5 └┌┐┘┴├┬
+        // 6 ┤┼─│└┌┐
+        //
+        // extra = ┼
+        let reachable = reachable_positions(&state);
+        assert_eq!(reachable.len(), state.reachable_by_player().len());
+        assert!(reachable.contains(&(1, 1)));
+
+        let tiles = widget_grid(&state, true, false);
+        assert!(reachable.iter().all(|&pos| tiles[pos].reachable));
+        assert_eq!(
+            tiles
+                .iter()
+                .flat_map(|row| row.iter())
+                .filter(|tile| tile.reachable)
+                .count(),
+            reachable.len()
+        );
+        let tiles = widget_grid(&state, false, false);
+        assert!(tiles
+            .iter()
+            .flat_map(|row| row.iter())
+            .all(|tile| !tile.reachable));
+    }
+
+    #[test]
+    fn test_widget_grid_goals() {
+        let mut state = State::<FullPlayerInfo>::default();
+        state.add_player(FullPlayerInfo::new(
+            (1, 1),
+            (1, 1),
+            (3, 3),
+            ColorName::Red.into(),
+        ));
+        state.add_player(FullPlayerInfo::new(
+            (5, 5),
+            (5, 3),
+            (1, 5),
+            ColorName::Blue.into(),
+        ));
+
+        let tiles = widget_grid(&state, false, true);
+        assert_eq!(tiles[(3, 3)].goal_color, Some(ColorName::Red.into()));
+        assert_eq!(tiles[(1, 5)].goal_color, Some(ColorName::Blue.into()));
+        assert_eq!(tiles[(1, 1)].home_color, Some(ColorName::Red.into()));
+        assert_eq!(tiles[(5, 5)].home_color, Some(ColorName::Blue.into()));
+        assert_eq!(
+            tiles[(5, 3)].player_colors,
+            vec![Color::from(ColorName::Blue)]
+        );
+        assert_eq!(
+            tiles
+                .iter()
+                .flat_map(|row| row.iter())
+                .filter(|tile| tile.goal_color.is_some())
+                .count(),
+            2
+        );
+
+        let tiles = widget_grid(&state, false, false);
+        assert!(tiles
+            .iter()
+            .flat_map(|row| row.iter())
+            .all(|tile| tile.goal_color.is_none()));
+    }
+
+    #[test]
+    fn test_legend_colors() {
+        let mut state = State::<FullPlayerInfo>::default();
+        assert!(legend_colors(&state).is_empty());
+
+        for color in [ColorName::White, ColorName::Red, ColorName::Black] {
+            state.add_player(FullPlayerInfo::new((1, 1), (1, 1), (3, 3), color.into()));
+        }
+        let expected: Vec<Color> = vec![
+            ColorName::Black.into(),
+            ColorName::Red.into(),
+            ColorName::White.into(),
+        ];
+        assert_eq!(legend_colors(&state), expected);
+
+        // the legend does not change as turns pass
+        state.next_player();
+        assert_eq!(legend_colors(&state), expected);
+    }
+
+    #[test]
+    fn test_from_transcript() {
+        let mut state = State::<FullPlayerInfo>::default();
+        state.add_player(FullPlayerInfo::new(
+            (1, 1),
+            (1, 1),
+            (3, 3),
+            ColorName::Red.into(),
+        ));
+        let mut transcript = vec![];
+        for _ in 0..3 {
+            serde_json::to_writer_pretty(&mut transcript, &JsonRefereeState::from(state.clone()))
+                .unwrap();
+            state
+                .slide_and_insert(Slide::new_unchecked(0, CompassDirection::North))
+                .unwrap();
+        }
+
+        let observer = ObserverGUI::from_transcript(transcript.as_slice()).unwrap();
+        assert_eq!(observer.states.lock().unwrap().len(), 3);
+        assert!(observer.is_game_over());
+
+        assert!(ObserverGUI::from_transcript("[1, 2]".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_latest_state() {
+        let mut observer = ObserverGUI::default();
+        let shared = observer.clone();
+        assert!(shared.latest_state().is_none());
+        assert!(!shared.is_game_over());
+
+        let mut state = State::<FullPlayerInfo>::default();
+        observer.recieve_state(state.clone());
+        state.add_player(FullPlayerInfo::new(
+            (1, 1),
+            (1, 1),
+            (3, 3),
+            ColorName::Red.into(),
+        ));
+        observer.recieve_state(state.clone());
+        observer.game_over();
+
+        assert_eq!(shared.latest_state(), Some(state));
+        assert!(shared.is_game_over());
+    }
+}
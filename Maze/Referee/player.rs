@@ -45,6 +45,13 @@ impl Player {
     }
 }
 
+/// Wraps `api` with a default `FullPlayerInfo`, to be replaced once the `Player` joins a game.
+impl From<Box<dyn PlayerApi>> for Player {
+    fn from(api: Box<dyn PlayerApi>) -> Self {
+        Player::new(api, FullPlayerInfo::default())
+    }
+}
+
 impl PublicPlayerInfo for Player {
     fn position(&self) -> Position {
         self.info.position()
@@ -87,6 +94,14 @@ impl PrivatePlayerInfo for Player {
     fn inc_goals_reached(&mut self) {
         self.info.inc_goals_reached()
     }
+
+    fn collect_goal(&mut self, goal: Position) {
+        self.info.collect_goal(goal)
+    }
+
+    fn goals_history(&self) -> &[Position] {
+        self.info.goals_history()
+    }
 }
 
 const TIMEOUT: Duration = Duration::from_secs(4);
@@ -115,6 +130,12 @@ impl PlayerApi for Player {
         let api = self.api.clone();
         run_with_timeout(move || api.lock().won(did_win), TIMEOUT)?
     }
+
+    /// Gives up on waiting for the player after `TIMEOUT`, since there is nothing to report.
+    fn game_start(&mut self, num_players: usize) {
+        let api = self.api.clone();
+        let _ = run_with_timeout(move || api.lock().game_start(num_players), TIMEOUT);
+    }
 }
 
 impl PartialEq for Player {
@@ -1,26 +1,87 @@
-use std::collections::VecDeque;
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hasher,
+    thread,
+    time::{Duration, Instant},
+};
 
-use crate::{json::JsonGameResult, player::Player};
+use crate::{
+    json::{JsonGameResult, JsonRefereeSnapshot},
+    player::Player,
+};
 use common::{
     board::{Board, DefaultBoard},
     grid::{squared_euclidian_distance, Position},
-    state::{FullPlayerInfo, PlayerInfo, PrivatePlayerInfo, PublicPlayerInfo, State},
+    json::Name,
+    state::{
+        FullPlayerInfo, PlayerInfo, PrivatePlayerInfo, PublicPlayerInfo, StableHasher, State,
+        TurnEffect,
+    },
 };
 use players::{player::PlayerApi, strategy::PlayerMove};
-use rand::{Rng, RngCore, SeedableRng};
+use rand::{seq::SliceRandom, Rng, RngCore, SeedableRng};
 use rand_chacha::ChaChaRng;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use crate::observer::Observer;
 
 /// The Result of calling `Referee::run_game(...)`.
 /// - The `winners` field contains all the winning players.
 /// - The `kicked` field contains all the players who misbehaved during the game.
+/// - The `turn_times` field contains the time each player spent taking turns. It is empty unless
+///   the `Referee`'s `Config::time_turns` is set.
 #[derive(Debug, Default, Clone, Serialize)]
 #[serde(into = "JsonGameResult")]
 pub struct GameResult {
     pub winners: Vec<Player>,
     pub kicked: Vec<Player>,
+    pub turn_times: HashMap<Name, TurnTimes>,
+}
+
+impl GameResult {
+    /// Returns a hash of the names and final `FullPlayerInfo` of the winners and the kicked
+    /// players, in order. Turn times are left out, since they differ between runs.
+    ///
+    /// The hash is made with a `StableHasher`, so running the same game with the same seed always
+    /// gives the same hash, and it can be pinned in tests to catch changes in how games play out.
+    pub fn outcome_hash(&self) -> u64 {
+        let mut hasher = StableHasher::default();
+        for players in [&self.winners, &self.kicked] {
+            hasher.write_len(players.len());
+            for player in players {
+                hasher.write_terminated(player.name().as_str());
+                hasher.write_player_info(&player.info);
+            }
+        }
+        hasher.finish()
+    }
+}
+
+/// The time a single `Player` spent taking their turns during a game.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TurnTimes {
+    /// The total time spent across all turns
+    pub total: Duration,
+    /// The number of turns taken
+    pub turns: u32,
+}
+
+impl TurnTimes {
+    /// Adds a turn that took `elapsed` time
+    fn record(&mut self, elapsed: Duration) {
+        self.total += elapsed;
+        self.turns += 1;
+    }
+
+    /// Returns the average time spent on a turn, or `Duration::ZERO` if no turns were taken
+    pub fn average(&self) -> Duration {
+        if self.turns == 0 {
+            Duration::ZERO
+        } else {
+            self.total / self.turns
+        }
+    }
 }
 
 /// Represents the winner of the game.
@@ -38,6 +99,17 @@ pub enum GameStatus {
     Tie,
     /// A single player won, causing the game to end
     Winner,
+    /// An `Observer` asked the `Referee` to stop the game between rounds
+    Stopped,
+}
+
+/// The reasons a `Referee` can refuse to start a game.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RefereeError {
+    /// The roster has more players than the `Board` has homes and goals for, or more than the
+    /// `Config::max_players` cap.
+    #[error("{players} players cannot play a game with at most {max} players!")]
+    TooManyPlayers { players: usize, max: usize },
 }
 
 /// Represents the effect of a `player::PlayerMove` on a State.
@@ -82,6 +154,116 @@ impl RefereeState for State<Player> {
     }
 }
 
+/// Describes the order in which goals are handed out to `Player`s.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GoalOrder {
+    /// Goals are handed out in the order given by `Board::possible_goals`.
+    #[default]
+    Sequential,
+    /// Goals are shuffled by the `Referee`'s random number generator before being handed out.
+    Shuffled,
+}
+
+/// The options a `Referee` runs its games with.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Does this Referee run games with multiple goals?
+    pub multiple_goals: bool,
+    /// The order in which goals are assigned to players.
+    pub goal_order: GoalOrder,
+    /// If set, the seed used to shuffle the turn order of `Player`s before a game starts. This is
+    /// kept separate from the `Referee`'s seed so shuffling does not change home and goal
+    /// assignment.
+    pub player_shuffle_seed: Option<u64>,
+    /// If set, the `Board` every game is played on. Otherwise, the `Referee` asks its players for
+    /// a `Board`.
+    pub board: Option<Board>,
+    /// Should the time each player spends on their turns be recorded in the `GameResult`?
+    pub time_turns: bool,
+    /// If set, the most players a game may start with. Games are always limited to the number of
+    /// possible homes and goals on their `Board`.
+    pub max_players: Option<usize>,
+    /// Should observers only be sent a state when it differs from the last state they were sent?
+    /// Only passing the turn to the next player does not count as a difference.
+    pub broadcast_on_change: bool,
+    /// Should players who have not collected any goals lose to every player who has? If not, they
+    /// are ranked by distance alongside the players who collected the most goals.
+    pub no_goal_penalty: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            multiple_goals: false,
+            goal_order: GoalOrder::default(),
+            player_shuffle_seed: None,
+            board: None,
+            time_turns: false,
+            max_players: None,
+            broadcast_on_change: false,
+            no_goal_penalty: true,
+        }
+    }
+}
+
+/// Everything needed to resume a game exactly where it was paused: the full `state` of the game,
+/// the goals that have not been handed out yet, and the `Config` of the `Referee` running it.
+///
+/// Serializes as a `JsonRefereeSnapshot`. Unlike a `JsonRefereeState`, this keeps the remaining
+/// goals and the number of goals each player has reached, so multiple goal games can be resumed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "JsonRefereeSnapshot", try_from = "JsonRefereeSnapshot")]
+pub struct RefereeSnapshot {
+    pub state: State<FullPlayerInfo>,
+    pub remaining_goals: VecDeque<Position>,
+    pub config: Config,
+}
+
+/// Receives notable events from a `Referee` while it runs a game.
+///
+/// Every method does nothing by default, so implementors only need to override the events they
+/// care about.
+pub trait RefereeLogger {
+    /// Called when the `Player` named `name` passes their turn.
+    fn log_pass(&mut self, _name: &Name) {}
+    /// Called when the `Player` named `name` is kicked from the game.
+    fn log_kick(&mut self, _name: &Name) {}
+    /// Called when the `Player` named `name` wins the game.
+    fn log_winner(&mut self, _name: &Name) {}
+    /// Called for every remaining `Player` when a game ends without a single winner, with the
+    /// squared euclidian `distance` from that `Player` to their goal.
+    fn log_distance(&mut self, _name: &Name, _distance: usize) {}
+}
+
+/// Decides which tiles are the homes of the `Player`s in a new game.
+pub trait HomePolicy {
+    /// Returns the homes of `n` `Player`s on `board`, in turn order.
+    fn assign_homes(&mut self, board: &Board, n: usize) -> Vec<Position>;
+}
+
+/// A `HomePolicy` that always assigns the same homes, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedHomes(pub Vec<Position>);
+
+impl HomePolicy for FixedHomes {
+    /// # Panics
+    /// Panics if there are fewer than `n` fixed homes.
+    fn assign_homes(&mut self, _board: &Board, n: usize) -> Vec<Position> {
+        assert!(
+            self.0.len() >= n,
+            "only {} homes for {n} players",
+            self.0.len()
+        );
+        self.0[..n].to_vec()
+    }
+}
+
+/// A `RefereeLogger` that ignores every event. This is the logger a `Referee` uses by default.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoLogger;
+
+impl RefereeLogger for NoLogger {}
+
 /// The `Referee` has all information necessary to run a game to completion. This struct itself
 /// contains very little, while its methods take in more interesting data (the current state,
 /// kicked players, etc.) as arguments.
@@ -89,45 +271,115 @@ pub struct Referee {
     /// Random number generation used for creating the lists of possible home and goal tiles to
     /// assign to players.
     rand: Box<dyn RngCore>,
-    /// Does this Referee run games with multiple goals?
-    multiple_goals: bool,
+    /// The options this `Referee` runs games with.
+    config: Config,
+    /// Receives the events that happen during this `Referee`'s games.
+    logger: Box<dyn RefereeLogger>,
+    /// Assigns the homes of new games. If `None`, every `Player` gets a random home from
+    /// `Board::possible_homes` that no other `Player` has.
+    home_policy: Option<Box<dyn HomePolicy>>,
+    /// The last state sent to observers, kept when `Config::broadcast_on_change` is set.
+    last_broadcast: Option<State<FullPlayerInfo>>,
+    /// The snapshot of the last game, if an observer stopped it between rounds.
+    stopped_game: Option<RefereeSnapshot>,
 }
 
 impl Referee {
     /// Constructs a new non-multiple-goal `Referee` with the given `seed`.
     pub fn new(seed: u64) -> Self {
+        Self::with_config(seed, Config::default())
+    }
+
+    /// Constructs a new `Referee` with the given `seed` and `config`.
+    pub fn with_config(seed: u64, config: Config) -> Self {
         Self {
             rand: Box::new(ChaChaRng::seed_from_u64(seed)),
-            multiple_goals: false,
+            config,
+            logger: Box::new(NoLogger),
+            home_policy: None,
+            last_broadcast: None,
+            stopped_game: None,
+        }
+    }
+
+    /// Resets this `Referee`'s random number generation to the state it would have had if it were
+    /// constructed with `seed`, so it can be reused for another game.
+    pub fn reseed(&mut self, seed: u64) {
+        self.rand = Box::new(ChaChaRng::seed_from_u64(seed));
+    }
+
+    /// Captures everything needed to resume the game in `state` later with `Referee::resume`,
+    /// given the goals that have not been handed out yet.
+    pub fn snapshot(
+        &self,
+        state: &State<Player>,
+        remaining_goals: &VecDeque<Position>,
+    ) -> RefereeSnapshot {
+        RefereeSnapshot {
+            state: state.to_full_state(),
+            remaining_goals: remaining_goals.clone(),
+            config: self.config.clone(),
         }
     }
 
+    /// Returns the snapshot of the last game this `Referee` ran, taken between rounds, if an
+    /// `Observer` stopped that game with `Observer::should_continue`.
+    pub fn stopped_game(&self) -> Option<&RefereeSnapshot> {
+        self.stopped_game.as_ref()
+    }
+
+    /// Replaces the `RefereeLogger` that receives the events of this `Referee`'s games.
+    pub fn set_logger(&mut self, logger: Box<dyn RefereeLogger>) {
+        self.logger = logger;
+    }
+
+    /// Replaces the random assignment of homes in this `Referee`'s games with `policy`.
+    pub fn set_home_policy(&mut self, policy: Box<dyn HomePolicy>) {
+        self.home_policy = Some(policy);
+    }
+
     /// Asks each `Player` in `players` to propose a `Board` and returns the chosen `Board`
     ///
+    /// The `observers` are asked in order to choose one of the proposals with
+    /// `Observer::choose_board`. If none of them do, the default 7x7 `Board` is used. Proposals are
+    /// made through `Player`, so a player that takes too long to propose is given up on like on any
+    /// other call.
+    ///
     /// # Panics  
     /// This method will panic is `player` is an empty vector
-    fn get_player_boards(&self, _players: &[Box<dyn PlayerApi>]) -> Board {
-        // FIXME: this should actually ask every player for a board
-        //let board = players[0].propose_board0(7, 7).unwrap();
-        // DOUBLE FIXME: We dont actually ask players to propose a board
-        DefaultBoard::<7, 7>::default_board()
+    fn get_player_boards(&self, players: &[Player], observers: &mut [Box<dyn Observer>]) -> Board {
+        // FIXME: the referee should pick a board from the proposals itself
+        let proposals = players
+            .iter()
+            .map(|player| player.propose_board0(7, 7).ok())
+            .collect::<Vec<_>>();
+        observers
+            .iter_mut()
+            .find_map(|observer| observer.choose_board(&proposals))
+            .and_then(|idx| proposals.get(idx).cloned().flatten())
+            .unwrap_or_else(DefaultBoard::<7, 7>::default_board)
     }
 
     /// Creates a vector of alternate goals based on `self.config.multiple_goals` and the given
     /// State.
     ///
     /// If `multiple_goals` is `true`, returns a vector of all possible goals in the `State`, with
-    /// the goals assigned to the `Players` in the game removed.
-    pub fn get_initial_goals(&self, state: &State<Player>) -> Vec<Position> {
-        if self.multiple_goals {
+    /// the goals assigned to the `Players` in the game removed. These goals are shuffled if
+    /// `self.config.goal_order` is `GoalOrder::Shuffled`.
+    pub fn get_initial_goals(&mut self, state: &State<Player>) -> Vec<Position> {
+        if self.config.multiple_goals {
             let assigned_goals: Vec<Position> =
                 state.player_info.iter().map(|pi| pi.goal()).collect();
 
-            state
+            let mut goals: Vec<Position> = state
                 .board
                 .possible_goals()
                 .filter(|g| !assigned_goals.contains(g))
-                .collect()
+                .collect();
+            if self.config.goal_order == GoalOrder::Shuffled {
+                goals.shuffle(&mut self.rand);
+            }
+            goals
         } else {
             vec![]
         }
@@ -136,22 +388,48 @@ impl Referee {
     /// Given a `Board` and the list of `Player`s, creates an initial `State` for this game.
     ///
     /// This will assign each player a Goal and a home tile, and set each `Player`'s current
-    /// position to be their home tile.
+    /// position to be their home tile. If `self.config.player_shuffle_seed` is set, the turn order
+    /// of `players` is shuffled first. Homes are assigned by `self.home_policy` if it is set.
+    ///
+    /// # Errors
+    /// Returns `RefereeError::TooManyPlayers` if there are more `players` than `board` has homes
+    /// or goals for, or more than `self.config.max_players`.
     fn make_initial_state(
         &mut self,
-        players: Vec<Box<dyn PlayerApi>>,
+        mut players: Vec<impl Into<Player>>,
         board: Board,
-    ) -> State<Player> {
+    ) -> Result<State<Player>, RefereeError> {
+        let max = self.max_players(&board);
+        if players.len() > max {
+            return Err(RefereeError::TooManyPlayers {
+                players: players.len(),
+                max,
+            });
+        }
+
+        if let Some(seed) = self.config.player_shuffle_seed {
+            players.shuffle(&mut ChaChaRng::seed_from_u64(seed));
+        }
+
         // The possible locations for homes
         let mut possible_homes = board.possible_homes().collect::<Vec<_>>();
 
         // The possible locations for goals, remove the filter here if goals become movable tiles.
         let mut possible_goals = board.possible_goals().collect::<VecDeque<_>>();
+        if self.config.goal_order == GoalOrder::Shuffled {
+            possible_goals.make_contiguous().shuffle(&mut self.rand);
+        }
+        let mut assigned_homes = self
+            .home_policy
+            .as_mut()
+            .map(|policy| policy.assign_homes(&board, players.len()).into_iter());
         let player_info = players
             .into_iter()
             .map(|player| {
-                let home: Position =
-                    possible_homes.remove(self.rand.gen_range(0..possible_homes.len()));
+                let home: Position = match &mut assigned_homes {
+                    Some(homes) => homes.next().expect("HomePolicy assigned too few homes"),
+                    None => possible_homes.remove(self.rand.gen_range(0..possible_homes.len())),
+                };
                 let goal: Position = possible_goals
                     .pop_front()
                     .expect("Did not have enough goals");
@@ -161,11 +439,22 @@ impl Referee {
                     goal,
                     (self.rand.gen(), self.rand.gen(), self.rand.gen()).into(),
                 );
-                Player::new(player, info)
+                let mut player: Player = player.into();
+                player.info = info;
+                player
             })
             .collect();
 
-        State::new(board, player_info)
+        Ok(State::new(board, player_info))
+    }
+
+    /// The most players a game on `board` can start with.
+    fn max_players(&self, board: &Board) -> usize {
+        let max = board
+            .possible_homes()
+            .count()
+            .min(board.possible_goals().count());
+        self.config.max_players.map_or(max, |cap| cap.min(max))
     }
 
     /// Communicates all public information of the current `state` and each `Player`'s private goal
@@ -173,29 +462,58 @@ impl Referee {
     ///
     /// If a `setup` call on any `Player` fails, that `Player` is removed from `state` and added to
     /// `kicked`.
-    pub fn broadcast_initial_state(&self, state: &mut State<Player>, kicked: &mut Vec<Player>) {
+    pub fn broadcast_initial_state(&mut self, state: &mut State<Player>, kicked: &mut Vec<Player>) {
         let mut player_state = state.to_player_state();
-        let total_players = state.player_info.len();
+        let total_players = state.player_count();
         for _ in 0..total_players {
             let player = state.current_player_info_mut();
             let goal = player.goal();
             match player.setup(Some(player_state.clone()), goal) {
                 Ok(_) => state.next_player(),
-                Err(_) => kicked.push(state.remove_player().unwrap()),
+                Err(_) => {
+                    let kicked_player = state.remove_player().unwrap();
+                    self.logger.log_kick(&kicked_player.name());
+                    kicked.push(kicked_player);
+                }
             }
             player_state.next_player();
         }
     }
 
     /// Communicates the current state to all observers
+    ///
+    /// If `Config::broadcast_on_change` is set, nothing is sent when the state is the same as the
+    /// last state sent, regardless of whose turn it is. See `State::eq_unordered_players`.
     fn broadcast_state_to_observers(
-        &self,
+        &mut self,
         state: &State<Player>,
         observers: &mut Vec<Box<dyn Observer>>,
     ) {
+        let full_state = state.to_full_state();
+        if self.config.broadcast_on_change {
+            let unchanged = self
+                .last_broadcast
+                .as_ref()
+                .is_some_and(|last| last.eq_unordered_players(&full_state));
+            if unchanged {
+                return;
+            }
+            self.last_broadcast = Some(full_state.clone());
+        }
+
+        for observer in observers {
+            observer.recieve_state(full_state.clone());
+        }
+    }
+
+    /// Asks every observer if the game should continue after a round. Every observer is asked,
+    /// even after one of them has asked to stop.
+    fn observers_should_continue(&self, observers: &mut Vec<Box<dyn Observer>>) -> bool {
+        let mut should_continue = true;
         for observer in observers {
-            observer.recieve_state(state.to_full_state());
+            should_continue &= observer.should_continue();
         }
+        should_continue
     }
 
     /// Communicates that the game has ended to all observers
@@ -211,37 +529,41 @@ impl Referee {
     /// - If the player wins the game from their move, returns `MoveEffect::Won`
     /// - If the player doesn't win the game, but their move was valid, returns `MoveEffect::Moved`
     /// - If the player doesn't submit a valid move, or doesn't communicate correctly, returns `MoveEffect::Cheated`
+    ///
+    /// The move itself is applied with `State::take_referee_turn`, which describes how goals are
+    /// reached and games are won.
+    ///
+    /// When a player's home becomes their goal, every observer is told with
+    /// `Observer::goal_is_now_home`.
     fn process_move(
-        &self,
+        &mut self,
         state: &mut State<Player>,
         observers: &mut Vec<Box<dyn Observer>>,
         remaining_goals: &mut VecDeque<Position>,
-        PlayerMove {
-            slide,
-            rotations,
-            destination,
-        }: PlayerMove,
+        player_move: PlayerMove,
     ) -> MoveEffect {
-        if state.try_move(slide, rotations, destination).is_err() {
-            return MoveEffect::Cheated;
-        }
-
-        // check if the current player just won
-        if state.player_reached_home()
-            && state.player_reached_goal()
-            && remaining_goals.is_empty()
-            // DONE: (This is hack awaiting spec clarification).await
-            && state.current_player_info().get_goals_reached() > 0
-        {
-            self.broadcast_state_to_observers(state, observers);
-            // this player wins
-            return MoveEffect::Won;
+        let goes_home = remaining_goals.is_empty();
+        let goals_reached = state.current_player_info().get_goals_reached();
+        match state.take_referee_turn(player_move, remaining_goals) {
+            TurnEffect::Cheated => return MoveEffect::Cheated,
+            TurnEffect::Won => {
+                self.broadcast_state_to_observers(state, observers);
+                // this player wins
+                return MoveEffect::Won;
+            }
+            TurnEffect::Moved => {}
         }
 
         // If the player moved and did not reach a goal, return `Moved`
-        if !state.update_current_player_goal(remaining_goals) {
+        if state.current_player_info().get_goals_reached() == goals_reached {
             return MoveEffect::Moved;
         }
+        if goes_home {
+            let color = state.current_player_info().color();
+            for observer in observers.iter_mut() {
+                observer.goal_is_now_home(color.clone());
+            }
+        }
 
         // setup the player with their new goal
         let goal = state.current_player_info().goal();
@@ -256,14 +578,17 @@ impl Referee {
     ///
     /// Returns `true` if moving to the next player succeeded, `false` if there are no more players
     fn next_player(
-        &self,
+        &mut self,
         state: &mut State<Player>,
         kicked: &mut Vec<Player>,
         should_kick: bool,
     ) -> bool {
         if should_kick {
             match state.remove_player() {
-                Ok(kicked_player) => kicked.push(kicked_player),
+                Ok(kicked_player) => {
+                    self.logger.log_kick(&kicked_player.name());
+                    kicked.push(kicked_player);
+                }
                 Err(_) => return false,
             };
         } else {
@@ -281,16 +606,25 @@ impl Referee {
         observers: &mut Vec<Box<dyn Observer>>,
         kicked: &mut Vec<Player>,
         remaining_goals: &mut VecDeque<Position>,
+        turn_times: &mut HashMap<Name, TurnTimes>,
     ) -> Option<GameStatus> {
         let mut num_kicked = 0;
         let mut num_passed = 0;
-        let players_in_round = state.player_info.len();
+        let players_in_round = state.player_count();
 
         for _idx in 0..players_in_round {
-            let should_kick = if let Ok(player_action) = state
+            let start = Instant::now();
+            let turn = state
                 .current_player_info()
-                .take_turn(state.to_player_state())
-            {
+                .take_turn(state.to_player_state());
+            if self.config.time_turns {
+                turn_times
+                    .entry(state.current_player_info().name())
+                    .or_default()
+                    .record(start.elapsed());
+            }
+
+            let should_kick = if let Ok(player_action) = turn {
                 if let Some(player_move) = player_action {
                     match self.process_move(state, observers, remaining_goals, player_move) {
                         MoveEffect::Won => return Some(GameStatus::Winner),
@@ -298,7 +632,7 @@ impl Referee {
                         MoveEffect::Moved => false,
                     }
                 } else {
-                    eprintln!("received PASS from {}", state.current_player_info().name());
+                    self.logger.log_pass(&state.current_player_info().name());
                     num_passed += 1;
                     false
                 }
@@ -327,7 +661,12 @@ impl Referee {
 
     /// Runs an entire game from the given `state` and `remaining_goals`.
     ///
-    /// Returns a `GameResult` containing the `winners` and `kicked` Players.
+    /// Returns a `GameResult` containing the `winners` and `kicked` Players. If every player is
+    /// kicked, the game ends as a tie with no winners, and every player is in `kicked`.
+    ///
+    /// `observers` receive the full state before any `Player` is set up, and again after setup if
+    /// any `Player` was kicked during it. Every `Player` left after setup is then told that the
+    /// game is starting.
     pub fn run_from_state(
         &mut self,
         state: &mut State<Player>,
@@ -335,28 +674,60 @@ impl Referee {
         mut remaining_goals: VecDeque<Position>,
     ) -> GameResult {
         let mut kicked = vec![];
+        let mut turn_times = HashMap::new();
+        self.last_broadcast = None;
+        self.stopped_game = None;
         // loop until game is over
         // - ask each player for a turn
         // - check if that player won
-        self.broadcast_initial_state(state, &mut kicked);
+        // observers see every player before anyone can be kicked during setup
         self.broadcast_state_to_observers(state, observers);
+        self.broadcast_initial_state(state, &mut kicked);
+        if !kicked.is_empty() {
+            self.broadcast_state_to_observers(state, observers);
+        }
+        let num_players = state.player_count();
+        for player in state.player_info.iter_mut() {
+            player.game_start(num_players);
+        }
 
         const ROUNDS: usize = 1000;
 
         let mut ended_early = GameStatus::NoMoreRounds;
 
         for _ in 0..ROUNDS {
-            if let Some(status) =
-                self.run_round(state, observers, &mut kicked, &mut remaining_goals)
-            {
+            if let Some(status) = self.run_round(
+                state,
+                observers,
+                &mut kicked,
+                &mut remaining_goals,
+                &mut turn_times,
+            ) {
                 ended_early = status;
                 break;
             };
+            if !self.observers_should_continue(observers) {
+                self.stopped_game = Some(self.snapshot(state, &remaining_goals));
+                ended_early = GameStatus::Stopped;
+                break;
+            }
         }
         self.broadcast_game_over_to_observers(observers);
-        let (mut winners, losers) = Referee::calculate_winners(state, ended_early);
-        Referee::broadcast_winners(&mut winners, losers, &mut kicked);
-        GameResult { winners, kicked }
+        if ended_early != GameStatus::Winner {
+            for player in state.player_info.iter() {
+                self.logger.log_distance(
+                    &player.name(),
+                    squared_euclidian_distance(&player.position(), &player.goal()),
+                );
+            }
+        }
+        let (mut winners, losers) = self.calculate_winners(state, ended_early);
+        self.broadcast_winners(&mut winners, losers, &mut kicked);
+        GameResult {
+            winners,
+            kicked,
+            turn_times,
+        }
     }
 
     /// Returns a tuple of two `Vec<Box<dyn Player>>`. The first of these vectors contains all
@@ -368,31 +739,32 @@ impl Referee {
     /// - Otherwise, winners are calculated by getting the players who collected the maximum number
     /// of goals, and finding all players who share a minimum distance to their next goal.
     ///
+    /// Because only players with the maximum number of goals are considered, a player that has not
+    /// collected any goals can only win when no player has collected a goal. If
+    /// `Config::no_goal_penalty` is unset, players without goals are considered as well.
+    ///
     /// The losers are calculated as all `Player`s in `state` that did not win.
     #[allow(clippy::type_complexity)]
     pub fn calculate_winners(
+        &self,
         state: &State<Player>,
         ended_early: GameStatus,
     ) -> (Vec<Player>, Vec<Player>) {
         let mut losers = vec![];
 
-        if state.player_info.is_empty() {
+        if state.is_empty() {
             return (vec![], vec![]);
         }
 
         let players_to_check = {
-            let max_goals = state
-                .player_info
-                .iter()
-                .map(|pi| pi.get_goals_reached())
-                .max()
-                .unwrap_or(0);
+            let max_goals = state.max_goals_reached();
             state
                 .player_info
                 .iter()
                 .cloned()
                 .fold(vec![], |mut acc, player| {
-                    if player.get_goals_reached() == max_goals {
+                    let goals = player.get_goals_reached();
+                    if goals == max_goals || (!self.config.no_goal_penalty && goals == 0) {
                         acc.push(player);
                     } else {
                         losers.push(player);
@@ -421,19 +793,13 @@ impl Referee {
             .map(|pi| squared_euclidian_distance(&pi.position(), &pi.goal()))
             .min()
             .unwrap_or(usize::MAX);
-        dbg!(min_dist);
 
         players_to_check
             .into_iter()
             .fold((vec![], losers), |(mut winners, mut losers), player| {
                 let goal_to_measure = player.goal();
 
-                if min_dist
-                    == dbg!(squared_euclidian_distance(
-                        &player.position(),
-                        &goal_to_measure
-                    ))
-                {
+                if min_dist == squared_euclidian_distance(&player.position(), &goal_to_measure) {
                     winners.push(player);
                 } else {
                     losers.push(player);
@@ -442,8 +808,11 @@ impl Referee {
             })
     }
 
-    /// Communicates if a player won to all `Player`s in the given tuple of winners and losers
+    /// Communicates if a player won to all `Player`s in the given tuple of winners and losers.
+    ///
+    /// Winners that fail to receive this message are kicked instead of winning.
     fn broadcast_winners(
+        &mut self,
         winners: &mut Vec<Player>,
         mut losers: Vec<Player>,
         kicked: &mut Vec<Player>,
@@ -455,7 +824,12 @@ impl Referee {
             }
         }
         for idx in kicked_winners.into_iter().rev() {
-            kicked.push(winners.remove(idx));
+            let kicked_winner = winners.remove(idx);
+            self.logger.log_kick(&kicked_winner.name());
+            kicked.push(kicked_winner);
+        }
+        for winner in winners.iter() {
+            self.logger.log_winner(&winner.name());
         }
 
         let mut kicked_losers = vec![];
@@ -465,34 +839,119 @@ impl Referee {
             }
         }
         for idx in kicked_losers.into_iter().rev() {
-            kicked.push(losers.remove(idx));
+            let kicked_loser = losers.remove(idx);
+            self.logger.log_kick(&kicked_loser.name());
+            kicked.push(kicked_loser);
         }
     }
 
+    /// Resumes the game saved in `snapshot`, using the `Config` it was saved with.
+    ///
+    /// `players` are paired with the players in `snapshot.state` in turn order.
+    ///
+    /// # Panics
+    /// This method panics if there are not exactly as many `players` as there are players in
+    /// `snapshot.state`.
+    pub fn resume(
+        &mut self,
+        snapshot: RefereeSnapshot,
+        players: Vec<Box<dyn PlayerApi>>,
+        mut observers: Vec<Box<dyn Observer>>,
+    ) -> GameResult {
+        let RefereeSnapshot {
+            state,
+            remaining_goals,
+            config,
+        } = snapshot;
+        assert_eq!(
+            players.len(),
+            state.player_info.len(),
+            "every player in the snapshot needs a PlayerApi"
+        );
+
+        self.config = config;
+        let mut state = State {
+            board: state.board,
+            player_info: players
+                .into_iter()
+                .zip(state.player_info)
+                .map(|(api, info)| Player::new(api, info))
+                .collect(),
+            previous_slide: state.previous_slide,
+        };
+        self.run_from_state(&mut state, &mut observers, remaining_goals)
+    }
+
     /// Runs the game given the age-sorted `Vec<Box<dyn Player>>`, `players`.
+    ///
+    /// If the game cannot start, nobody plays and an empty `GameResult` is returned. Use
+    /// `Referee::try_run_game` to find out why.
     pub fn run_game(
         &mut self,
         players: Vec<Box<dyn PlayerApi>>,
-        mut observers: Vec<Box<dyn Observer>>,
+        observers: Vec<Box<dyn Observer>>,
     ) -> GameResult {
-        // Iterate over players to get their proposed boards
+        self.try_run_game(players, observers).unwrap_or_default()
+    }
+
+    /// Runs the game given the age-sorted `Vec<Box<dyn Player>>`, `players`.
+    ///
+    /// # Errors
+    /// Returns `RefereeError::TooManyPlayers` if there are too many `players` for the chosen
+    /// `Board` or the `Referee`'s `Config::max_players`.
+    pub fn try_run_game(
+        &mut self,
+        players: Vec<Box<dyn PlayerApi>>,
+        mut observers: Vec<Box<dyn Observer>>,
+    ) -> Result<GameResult, RefereeError> {
+        // Use the configured board if there is one, otherwise iterate over players to get their
+        // proposed boards
         // - for now, use the first players proposed board
-        let board = self.get_player_boards(&players);
+        let players = players.into_iter().map(Player::from).collect::<Vec<_>>();
+        let board = match &self.config.board {
+            Some(board) => board.clone(),
+            None => self.get_player_boards(&players, &mut observers),
+        };
 
         // Create `State` from the chosen board
         // Assign each player a home + goal + current position
         // communicate initial state to all players
-        let mut state = self.make_initial_state(players, board);
+        let mut state = self.make_initial_state(players, board)?;
 
         let goals = self.get_initial_goals(&state).into();
-        self.run_from_state(&mut state, &mut observers, goals)
+        Ok(self.run_from_state(&mut state, &mut observers, goals))
     }
 }
 
+/// Runs one game for every roster in `rosters`, each on its own thread with its own `Referee`.
+///
+/// The game for `rosters[i]` is run by `Referee::new(base_seed + i)`, so the results are the same as
+/// running the games one after another with those seeds. The results are returned in the same
+/// order as `rosters`.
+///
+/// # Panics
+/// This function panics if any of the games panics.
+pub fn run_many_parallel(rosters: Vec<Vec<Box<dyn PlayerApi>>>, base_seed: u64) -> Vec<GameResult> {
+    let handles = rosters
+        .into_iter()
+        .enumerate()
+        .map(|(idx, players)| {
+            thread::spawn(move || Referee::new(base_seed + idx as u64).run_game(players, vec![]))
+        })
+        .collect::<Vec<_>>();
+    handles
+        .into_iter()
+        .map(|handle| handle.join().expect("a game panicked"))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::{collections::VecDeque, sync::Arc};
+    use std::{
+        collections::{HashSet, VecDeque},
+        sync::Arc,
+    };
 
     use common::{
         board::Slide,
@@ -504,10 +963,12 @@ mod tests {
     };
     use parking_lot::Mutex;
     use players::{
-        player::{LocalPlayer, PlayerApi, PlayerApiResult},
+        bad_player::{BadFM, BadPlayer, BadPlayerLoop},
+        player::{LocalPlayer, PlayerApi, PlayerApiError, PlayerApiResult},
         strategy::{NaiveStrategy, PlayerAction, PlayerMove},
     };
 
+    use crate::observer::ObserverGUI;
     use crate::referee::{GameResult, GameStatus, MoveEffect, Player, PrivatePlayerInfo, Referee};
 
     #[derive(Debug, Default, Clone)]
@@ -516,6 +977,8 @@ mod tests {
         state: Arc<Mutex<Option<State<PlayerInfo>>>>,
         goal: Arc<Mutex<Option<Position>>>,
         won: Arc<Mutex<Option<bool>>>,
+        /// The number of players and the number of turns taken so far, for each `game_start`
+        game_starts: Arc<Mutex<Vec<(usize, usize)>>>,
     }
 
     impl PlayerApi for MockPlayer {
@@ -547,33 +1010,179 @@ mod tests {
             *self.won.lock() = Some(did_win);
             Ok(())
         }
+
+        fn game_start(&mut self, num_players: usize) {
+            let turns_taken = *self.turns_taken.lock();
+            self.game_starts.lock().push((num_players, turns_taken));
+        }
     }
 
     #[test]
     fn test_get_player_boards() {
         let referee = Referee {
             rand: Box::new(ChaChaRng::seed_from_u64(0)),
-            multiple_goals: false,
+            config: Config {
+                multiple_goals: false,
+                ..Default::default()
+            },
+            logger: Box::new(NoLogger),
+            home_policy: None,
+            last_broadcast: None,
+            stopped_game: None,
         };
-        let mut players: Vec<Box<dyn PlayerApi>> = vec![Box::new(LocalPlayer::new(
+        let mut players: Vec<Player> = vec![Player::from(Box::new(LocalPlayer::new(
             Name::from_static("bill"),
             NaiveStrategy::Euclid,
-        ))];
-        let board = referee.get_player_boards(&players);
+        )) as Box<dyn PlayerApi>)];
+        let board = referee.get_player_boards(&players, &mut []);
         assert_eq!(board, DefaultBoard::<7, 7>::default_board());
-        players.push(Box::new(MockPlayer::default()));
+        players.push(Player::from(
+            Box::new(MockPlayer::default()) as Box<dyn PlayerApi>
+        ));
         players.rotate_left(1);
-        let _board = referee.get_player_boards(&players);
+        let _board = referee.get_player_boards(&players, &mut []);
         // TODO: fix this
         //  it should be a 3 by 3 board
         //assert_eq!(board, DefaultBoard::<7, 7>::default_board());
     }
 
+    #[test]
+    fn test_observer_choose_board() {
+        struct ChoosingObserver(Option<usize>);
+
+        impl Observer for ChoosingObserver {
+            fn recieve_state(&mut self, _state: State<FullPlayerInfo>) {}
+
+            fn game_over(&mut self) {}
+
+            fn choose_board(&mut self, _proposals: &[Option<Board>]) -> Option<usize> {
+                self.0
+            }
+        }
+
+        let referee = Referee::new(0);
+        // bill proposes the default 7x7 board, bob proposes the default 3x3 board
+        let players: Vec<Player> = vec![
+            Box::new(LocalPlayer::new(
+                Name::from_static("bill"),
+                NaiveStrategy::Euclid,
+            )) as Box<dyn PlayerApi>,
+            Box::new(MockPlayer::default()),
+        ]
+        .into_iter()
+        .map(Player::from)
+        .collect();
+
+        let mut observers: Vec<Box<dyn Observer>> = vec![Box::new(ChoosingObserver(Some(1)))];
+        assert_eq!(
+            referee.get_player_boards(&players, &mut observers),
+            DefaultBoard::<3, 3>::default_board()
+        );
+
+        // the first observer to choose a board decides
+        let mut observers: Vec<Box<dyn Observer>> = vec![
+            Box::new(ObserverGUI::default()),
+            Box::new(ChoosingObserver(Some(1))),
+            Box::new(ChoosingObserver(Some(0))),
+        ];
+        assert_eq!(
+            referee.get_player_boards(&players, &mut observers),
+            DefaultBoard::<3, 3>::default_board()
+        );
+
+        for choice in [None, Some(2)] {
+            let mut observers: Vec<Box<dyn Observer>> = vec![
+                Box::new(ObserverGUI::default()),
+                Box::new(ChoosingObserver(choice)),
+            ];
+            assert_eq!(
+                referee.get_player_boards(&players, &mut observers),
+                DefaultBoard::<7, 7>::default_board()
+            );
+        }
+    }
+
+    #[test]
+    fn test_observer_choose_board_failed_proposal() {
+        struct NoBoardPlayer(MockPlayer);
+
+        impl PlayerApi for NoBoardPlayer {
+            fn name(&self) -> Name {
+                self.0.name()
+            }
+
+            fn propose_board0(&self, _cols: u32, _rows: u32) -> PlayerApiResult<Board> {
+                Err(PlayerApiError::Timeout)
+            }
+
+            fn setup(
+                &mut self,
+                state: Option<State<PlayerInfo>>,
+                goal: Position,
+            ) -> PlayerApiResult<()> {
+                self.0.setup(state, goal)
+            }
+
+            fn take_turn(&self, state: State<PlayerInfo>) -> PlayerApiResult<PlayerAction> {
+                self.0.take_turn(state)
+            }
+
+            fn won(&mut self, did_win: bool) -> PlayerApiResult<()> {
+                self.0.won(did_win)
+            }
+        }
+
+        struct ChoosingObserver(usize);
+
+        impl Observer for ChoosingObserver {
+            fn recieve_state(&mut self, _state: State<FullPlayerInfo>) {}
+
+            fn game_over(&mut self) {}
+
+            fn choose_board(&mut self, proposals: &[Option<Board>]) -> Option<usize> {
+                assert_eq!(proposals.len(), 2);
+                assert!(proposals[0].is_none());
+                Some(self.0)
+            }
+        }
+
+        let referee = Referee::new(0);
+        // the first player fails to propose a board, bob proposes the default 3x3 board
+        let players: Vec<Player> = vec![
+            Box::new(NoBoardPlayer(MockPlayer::default())) as Box<dyn PlayerApi>,
+            Box::new(MockPlayer::default()),
+        ]
+        .into_iter()
+        .map(Player::from)
+        .collect();
+
+        // the index of bob's proposal still matches bob's place in the turn order
+        let mut observers: Vec<Box<dyn Observer>> = vec![Box::new(ChoosingObserver(1))];
+        assert_eq!(
+            referee.get_player_boards(&players, &mut observers),
+            DefaultBoard::<3, 3>::default_board()
+        );
+
+        // choosing the missing proposal falls back to the default board
+        let mut observers: Vec<Box<dyn Observer>> = vec![Box::new(ChoosingObserver(0))];
+        assert_eq!(
+            referee.get_player_boards(&players, &mut observers),
+            DefaultBoard::<7, 7>::default_board()
+        );
+    }
+
     #[test]
     fn test_get_initial_goals() {
-        let referee = Referee {
+        let mut referee = Referee {
             rand: Box::new(ChaChaRng::seed_from_u64(0)),
-            multiple_goals: false,
+            config: Config {
+                multiple_goals: false,
+                ..Default::default()
+            },
+            logger: Box::new(NoLogger),
+            home_policy: None,
+            last_broadcast: None,
+            stopped_game: None,
         };
 
         let state = State::default();
@@ -598,9 +1207,16 @@ mod tests {
         state.add_player(bob);
         state.add_player(jill);
 
-        let referee = Referee {
+        let mut referee = Referee {
             rand: Box::new(ChaChaRng::seed_from_u64(0)),
-            multiple_goals: true,
+            config: Config {
+                multiple_goals: true,
+                ..Default::default()
+            },
+            logger: Box::new(NoLogger),
+            home_policy: None,
+            last_broadcast: None,
+            stopped_game: None,
         };
 
         let init_goals = referee.get_initial_goals(&state);
@@ -611,13 +1227,22 @@ mod tests {
     #[test]
     fn test_make_initial_state() {
         let mut referee = Referee {
-            rand: Box::new(ChaChaRng::seed_from_u64(1)), // Seed 0 makes the first player have the
-            multiple_goals: true,
-            // same home and goal tile
+            // Seed 0 makes the first player have the same home and goal tile
+            rand: Box::new(ChaChaRng::seed_from_u64(1)),
+            config: Config {
+                multiple_goals: true,
+                ..Default::default()
+            },
+            logger: Box::new(NoLogger),
+            home_policy: None,
+            last_broadcast: None,
+            stopped_game: None,
         };
         let player = Box::new(MockPlayer::default());
         let players: Vec<Box<dyn PlayerApi>> = vec![player, Box::new(MockPlayer::default())];
-        let mut state = referee.make_initial_state(players, DefaultBoard::<7, 7>::default_board());
+        let mut state = referee
+            .make_initial_state(players, DefaultBoard::<7, 7>::default_board())
+            .unwrap();
         assert_eq!(state.current_player_info().home(), (1, 3));
         assert_eq!(state.current_player_info().goal(), (1, 1));
         assert_eq!(state.current_player_info().position(), (1, 3));
@@ -628,46 +1253,286 @@ mod tests {
     }
 
     #[test]
-    fn test_broadcast_inital_state() {
-        let mut referee = Referee {
-            multiple_goals: false,
-            rand: Box::new(ChaChaRng::seed_from_u64(0)),
+    fn test_make_initial_state_home_policy() {
+        let players = || -> Vec<Box<dyn PlayerApi>> {
+            (0..3)
+                .map(|_| Box::new(MockPlayer::default()) as Box<dyn PlayerApi>)
+                .collect()
         };
-        let player = Box::new(MockPlayer::default());
-        let players: Vec<Box<dyn PlayerApi>> = vec![player.clone()];
-        let mut state = referee.make_initial_state(players, DefaultBoard::<7, 7>::default_board());
-        assert_eq!(*player.goal.lock(), None);
-        referee.broadcast_initial_state(&mut state, &mut vec![]);
-        assert_eq!(
-            state.current_player_info().goal(),
-            player.goal.lock().unwrap()
-        );
+        let board: Board = DefaultBoard::<7, 7>::default_board();
+
+        let mut referee = Referee::new(0);
+        referee.set_home_policy(Box::new(FixedHomes(vec![(5, 5), (1, 1), (3, 5), (5, 1)])));
+        let state = referee
+            .make_initial_state(players(), board.clone())
+            .unwrap();
+        let homes = state
+            .player_info
+            .iter()
+            .map(|pl| pl.home())
+            .collect::<Vec<_>>();
+        assert_eq!(homes, vec![(5, 5), (1, 1), (3, 5)]);
+        assert!(state
+            .player_info
+            .iter()
+            .all(|pl| pl.position() == pl.home()));
+
+        for seed in 0..10 {
+            let mut referee = Referee::new(seed);
+            let state = referee
+                .make_initial_state(players(), board.clone())
+                .unwrap();
+            let homes = state
+                .player_info
+                .iter()
+                .map(|pl| pl.home())
+                .collect::<HashSet<_>>();
+            assert_eq!(homes.len(), 3);
+            assert!(homes
+                .iter()
+                .all(|home| board.possible_homes().any(|pos| &pos == home)));
+        }
     }
 
     #[test]
-    fn test_next_player() {
-        let mut state = State::default();
-        let bob = Player::new(
-            Box::new(MockPlayer::default()),
-            FullPlayerInfo::new((1, 1), (1, 1), (0, 5), Color::from(ColorName::Red)),
+    fn test_too_many_players() {
+        let players = |n: usize| {
+            (0..n)
+                .map(|_| Box::new(MockPlayer::default()) as Box<dyn PlayerApi>)
+                .collect::<Vec<_>>()
+        };
+
+        // the only immovable tile on the default 3x3 board is (1, 1)
+        let mut referee = Referee::with_config(
+            0,
+            Config {
+                board: Some(DefaultBoard::<3, 3>::default_board()),
+                ..Default::default()
+            },
         );
-        let jill = Player::new(
-            Box::new(LocalPlayer::new(
-                Name::from_static("jill"),
-                NaiveStrategy::Riemann,
-            )),
-            FullPlayerInfo::new((1, 3), (1, 3), (0, 3), Color::from(ColorName::Blue)),
+        assert_eq!(
+            referee.try_run_game(players(2), vec![]).unwrap_err(),
+            RefereeError::TooManyPlayers { players: 2, max: 1 }
         );
-        state.add_player(bob);
-        state.add_player(jill);
+        let result = referee.run_game(players(2), vec![]);
+        assert!(result.winners.is_empty());
+        assert!(result.kicked.is_empty());
+
+        let mut referee = Referee::with_config(
+            0,
+            Config {
+                max_players: Some(2),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            referee
+                .make_initial_state(players(3), DefaultBoard::<7, 7>::default_board())
+                .unwrap_err(),
+            RefereeError::TooManyPlayers { players: 3, max: 2 }
+        );
+        assert!(referee
+            .make_initial_state(players(2), DefaultBoard::<7, 7>::default_board())
+            .is_ok());
+    }
 
-        assert_eq!(state.player_info[0].name(), "bob");
-        assert_eq!(state.player_info[0].color(), Color::from(ColorName::Red));
-        assert_eq!(state.player_info[1].name(), "jill");
-        assert_eq!(state.player_info[1].color(), Color::from(ColorName::Blue));
-        state.next_player();
-        assert_eq!(state.player_info[1].name(), "bob");
-        assert_eq!(state.player_info[1].color(), Color::from(ColorName::Red));
+    #[test]
+    fn test_make_initial_state_goal_order() {
+        let assigned_goals = |goal_order: GoalOrder| {
+            let mut referee = Referee::with_config(
+                1,
+                Config {
+                    multiple_goals: true,
+                    goal_order,
+                    ..Default::default()
+                },
+            );
+            let players: Vec<Box<dyn PlayerApi>> = (0..4)
+                .map(|_| Box::new(MockPlayer::default()) as Box<dyn PlayerApi>)
+                .collect();
+            let state = referee
+                .make_initial_state(players, DefaultBoard::<7, 7>::default_board())
+                .unwrap();
+            let goals: Vec<Position> = state.player_info.iter().map(|pi| pi.goal()).collect();
+            (goals, referee.get_initial_goals(&state))
+        };
+
+        let (sequential, _) = assigned_goals(GoalOrder::Sequential);
+        assert_eq!(sequential, vec![(1, 1), (1, 3), (1, 5), (3, 1)]);
+
+        let (shuffled, remaining) = assigned_goals(GoalOrder::Shuffled);
+        assert_ne!(shuffled, sequential);
+        assert_eq!(remaining.len(), 5);
+        assert!(remaining.iter().all(|goal| !shuffled.contains(goal)));
+
+        // the same seed always produces the same assignment
+        assert_eq!(assigned_goals(GoalOrder::Shuffled), (shuffled, remaining));
+    }
+
+    #[test]
+    fn test_reseed() {
+        let assignments = |referee: &mut Referee| {
+            let players: Vec<Box<dyn PlayerApi>> = ["a", "b", "c"]
+                .into_iter()
+                .map(|name| -> Box<dyn PlayerApi> {
+                    Box::new(LocalPlayer::new(
+                        Name::from_static(name),
+                        NaiveStrategy::Euclid,
+                    ))
+                })
+                .collect();
+            let state = referee
+                .make_initial_state(players, DefaultBoard::<7, 7>::default_board())
+                .unwrap();
+            state
+                .player_info
+                .iter()
+                .map(|pi| (pi.home(), pi.goal(), pi.color()))
+                .collect::<Vec<_>>()
+        };
+
+        let mut referee = Referee::new(0);
+        let first = assignments(&mut referee);
+        assert_eq!(first, assignments(&mut Referee::new(0)));
+
+        referee.reseed(0);
+        assert_eq!(assignments(&mut referee), first);
+
+        referee.reseed(5);
+        assert_eq!(assignments(&mut referee), assignments(&mut Referee::new(5)));
+    }
+
+    #[test]
+    fn test_make_initial_state_player_shuffle() {
+        let turn_order = |player_shuffle_seed: Option<u64>| {
+            let mut referee = Referee::with_config(
+                1,
+                Config {
+                    player_shuffle_seed,
+                    ..Default::default()
+                },
+            );
+            let players: Vec<Box<dyn PlayerApi>> = ["a", "b", "c", "d", "e", "f"]
+                .into_iter()
+                .map(|name| -> Box<dyn PlayerApi> {
+                    Box::new(LocalPlayer::new(
+                        Name::from_static(name),
+                        NaiveStrategy::Euclid,
+                    ))
+                })
+                .collect();
+            let state = referee
+                .make_initial_state(players, DefaultBoard::<7, 7>::default_board())
+                .unwrap();
+            state
+                .player_info
+                .iter()
+                .map(|pi| (pi.name(), pi.home()))
+                .collect::<Vec<_>>()
+        };
+
+        let unshuffled = turn_order(None);
+        let names: Vec<Name> = unshuffled.iter().map(|(name, _)| name.clone()).collect();
+        assert_eq!(names, ["a", "b", "c", "d", "e", "f"].map(Name::from_static));
+
+        let shuffled = turn_order(Some(7));
+        assert_ne!(shuffled, unshuffled);
+        assert_eq!(shuffled, turn_order(Some(7)));
+
+        // shuffling the players does not change which homes are handed out
+        let homes =
+            |order: &[(Name, Position)]| order.iter().map(|(_, home)| *home).collect::<Vec<_>>();
+        assert_eq!(homes(&shuffled), homes(&unshuffled));
+    }
+
+    #[test]
+    fn test_run_game_configured_board() {
+        let players = || -> Vec<Box<dyn PlayerApi>> {
+            vec![Box::new(LocalPlayer::new(
+                Name::from_static("bob"),
+                NaiveStrategy::Euclid,
+            ))]
+        };
+
+        let observer = ObserverGUI::default();
+        let mut referee = Referee::with_config(
+            0,
+            Config {
+                board: Some(DefaultBoard::<3, 3>::default_board()),
+                ..Default::default()
+            },
+        );
+        referee.run_game(players(), vec![Box::new(observer.clone())]);
+        let state = observer.latest_state().unwrap();
+        assert_eq!(state.board.num_cols(), 3);
+        assert_eq!(state.board.num_rows(), 3);
+
+        let observer = ObserverGUI::default();
+        let mut referee = Referee::new(0);
+        referee.run_game(players(), vec![Box::new(observer.clone())]);
+        let state = observer.latest_state().unwrap();
+        assert_eq!(state.board.num_cols(), 7);
+        assert_eq!(state.board.num_rows(), 7);
+        // slides never move the tiles in odd rows and columns, so they still match the board bob
+        // proposed
+        let default: Board = DefaultBoard::<7, 7>::default_board();
+        for col in (1..7).step_by(2) {
+            for row in (1..7).step_by(2) {
+                assert_eq!(state.board.grid[(col, row)], default.grid[(col, row)]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_broadcast_inital_state() {
+        let mut referee = Referee {
+            config: Config {
+                multiple_goals: false,
+                ..Default::default()
+            },
+            rand: Box::new(ChaChaRng::seed_from_u64(0)),
+            logger: Box::new(NoLogger),
+            home_policy: None,
+            last_broadcast: None,
+            stopped_game: None,
+        };
+        let player = Box::new(MockPlayer::default());
+        let players: Vec<Box<dyn PlayerApi>> = vec![player.clone()];
+        let mut state = referee
+            .make_initial_state(players, DefaultBoard::<7, 7>::default_board())
+            .unwrap();
+        assert_eq!(*player.goal.lock(), None);
+        referee.broadcast_initial_state(&mut state, &mut vec![]);
+        assert_eq!(
+            state.current_player_info().goal(),
+            player.goal.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_next_player() {
+        let mut state = State::default();
+        let bob = Player::new(
+            Box::new(MockPlayer::default()),
+            FullPlayerInfo::new((1, 1), (1, 1), (0, 5), Color::from(ColorName::Red)),
+        );
+        let jill = Player::new(
+            Box::new(LocalPlayer::new(
+                Name::from_static("jill"),
+                NaiveStrategy::Riemann,
+            )),
+            FullPlayerInfo::new((1, 3), (1, 3), (0, 3), Color::from(ColorName::Blue)),
+        );
+        state.add_player(bob);
+        state.add_player(jill);
+
+        assert_eq!(state.player_info[0].name(), "bob");
+        assert_eq!(state.player_info[0].color(), Color::from(ColorName::Red));
+        assert_eq!(state.player_info[1].name(), "jill");
+        assert_eq!(state.player_info[1].color(), Color::from(ColorName::Blue));
+        state.next_player();
+        assert_eq!(state.player_info[1].name(), "bob");
+        assert_eq!(state.player_info[1].color(), Color::from(ColorName::Red));
         assert_eq!(state.player_info[0].name(), "jill");
         assert_eq!(state.player_info[0].color(), Color::from(ColorName::Blue));
     }
@@ -690,7 +1555,7 @@ mod tests {
         state.add_player(jill);
 
         // as is, jill wins because it is closer to 1, 1
-        let (winners, losers) = Referee::calculate_winners(&state, GameStatus::Tie);
+        let (winners, losers) = Referee::new(0).calculate_winners(&state, GameStatus::Tie);
         assert_eq!(winners.len(), 1);
         assert_eq!(winners[0].name(), "jill");
         assert_eq!(losers.len(), 1);
@@ -711,7 +1576,7 @@ mod tests {
         state.add_player(bob);
         state.add_player(jill);
         // if bob has collected a goal, bob wins
-        let (winners, losers) = Referee::calculate_winners(&state, GameStatus::Tie);
+        let (winners, losers) = Referee::new(0).calculate_winners(&state, GameStatus::Tie);
         assert_eq!(winners.len(), 1);
         assert_eq!(winners[0].name(), "bob");
         assert_eq!(losers.len(), 1);
@@ -735,7 +1600,7 @@ mod tests {
         state.add_player(bob);
         state.add_player(jill);
         // bob wins because it is closer
-        let (winners, losers) = Referee::calculate_winners(&state, GameStatus::Tie);
+        let (winners, losers) = Referee::new(0).calculate_winners(&state, GameStatus::Tie);
         assert_eq!(winners.len(), 1);
         assert_eq!(winners[0].name(), "bob");
         assert_eq!(losers.len(), 1);
@@ -757,17 +1622,121 @@ mod tests {
         state.add_player(bob);
         state.add_player(jill);
         // both players win
-        let (winners, losers) = Referee::calculate_winners(&state, GameStatus::Tie);
+        let (winners, losers) = Referee::new(0).calculate_winners(&state, GameStatus::Tie);
         assert_eq!(winners[0].name(), "bob");
         assert_eq!(winners.len(), 2);
         assert_eq!(losers.len(), 0);
     }
 
+    #[test]
+    fn test_calculate_winners_no_goals() {
+        let make_state = |jill_goals: usize, bob_goals: usize| {
+            let mut state = State::default();
+            let mut bob = Player::new(
+                Box::new(MockPlayer::default()),
+                FullPlayerInfo::new((1, 1), (5, 5), (1, 1), Color::from(ColorName::Red)),
+            );
+            let mut jill = Player::new(
+                Box::new(LocalPlayer::new(
+                    Name::from_static("jill"),
+                    NaiveStrategy::Riemann,
+                )),
+                FullPlayerInfo::new((1, 5), (1, 1), (1, 1), Color::from(ColorName::Blue)),
+            );
+            (0..bob_goals).for_each(|_| bob.inc_goals_reached());
+            (0..jill_goals).for_each(|_| jill.inc_goals_reached());
+            // jill is the active player and sits on their goal
+            state.add_player(jill);
+            state.add_player(bob);
+            state
+        };
+
+        // jill is closer, but bob collected a goal, so jill never wins
+        for status in [GameStatus::Tie, GameStatus::Winner] {
+            let (winners, losers) = Referee::new(0).calculate_winners(&make_state(0, 1), status);
+            assert_eq!(winners.len(), 1);
+            assert_eq!(winners[0].name(), "bob");
+            assert_eq!(losers.len(), 1);
+            assert_eq!(losers[0].name(), "jill");
+        }
+
+        // once both have a goal, distance decides the tie
+        let (winners, losers) =
+            Referee::new(0).calculate_winners(&make_state(1, 1), GameStatus::Tie);
+        assert_eq!(winners.len(), 1);
+        assert_eq!(winners[0].name(), "jill");
+        assert_eq!(losers.len(), 1);
+
+        // without the penalty, jill is ranked with bob even though jill has no goals
+        let referee = Referee::with_config(
+            0,
+            Config {
+                no_goal_penalty: false,
+                ..Default::default()
+            },
+        );
+        let (winners, losers) = referee.calculate_winners(&make_state(0, 1), GameStatus::Tie);
+        assert_eq!(winners.len(), 1);
+        assert_eq!(winners[0].name(), "jill");
+        assert_eq!(losers.len(), 1);
+        assert_eq!(losers[0].name(), "bob");
+
+        // but players with goals are still ranked by how many they collected
+        let (winners, losers) = referee.calculate_winners(&make_state(1, 3), GameStatus::Tie);
+        assert_eq!(winners.len(), 1);
+        assert_eq!(winners[0].name(), "bob");
+        assert_eq!(losers.len(), 1);
+        assert_eq!(losers[0].name(), "jill");
+    }
+
+    #[test]
+    fn test_score_table_matches_winners() {
+        let mut state = State::default();
+        for (idx, (home, position, goal, color)) in [
+            ((1, 1), (0, 0), (1, 5), ColorName::Red),
+            ((1, 5), (1, 0), (1, 1), ColorName::Blue),
+            ((3, 3), (3, 3), (5, 5), ColorName::Green),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let mut player = Player::new(
+                Box::new(MockPlayer::default()),
+                FullPlayerInfo::new(home, position, goal, color.into()),
+            );
+            // blue and green have the most goals, and blue is closer to its goal
+            if idx > 0 {
+                player.inc_goals_reached();
+            }
+            state.add_player(player);
+        }
+
+        let table = state.score_table();
+        let max_goals = table.iter().map(|(_, goals, _)| *goals).max().unwrap();
+        let best = table
+            .iter()
+            .filter(|(_, goals, _)| *goals == max_goals)
+            .min_by_key(|(_, _, dist)| *dist)
+            .unwrap();
+        assert_eq!(best, &(ColorName::Blue.into(), 1, 1));
+
+        let (winners, _) = Referee::new(0).calculate_winners(&state, GameStatus::Tie);
+        assert_eq!(winners.len(), 1);
+        assert_eq!(winners[0].color(), best.0);
+    }
+
     #[test]
     fn test_broadcast_winners() {
         let mut referee = Referee {
             rand: Box::new(ChaChaRng::seed_from_u64(0)),
-            multiple_goals: false,
+            config: Config {
+                multiple_goals: false,
+                ..Default::default()
+            },
+            logger: Box::new(NoLogger),
+            home_policy: None,
+            last_broadcast: None,
+            stopped_game: None,
         };
 
         let player = Box::new(MockPlayer::default());
@@ -789,16 +1758,526 @@ mod tests {
         assert_eq!(*player.won.lock(), Some(false));
     }
 
+    #[derive(Debug, Default, Clone)]
+    struct CapturingLogger {
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl RefereeLogger for CapturingLogger {
+        fn log_pass(&mut self, name: &Name) {
+            self.events.lock().push(format!("pass {name}"));
+        }
+
+        fn log_kick(&mut self, name: &Name) {
+            self.events.lock().push(format!("kick {name}"));
+        }
+
+        fn log_winner(&mut self, name: &Name) {
+            self.events.lock().push(format!("winner {name}"));
+        }
+    }
+
+    #[test]
+    fn test_referee_logger() {
+        let logger = CapturingLogger::default();
+        let mut referee = Referee::new(0);
+        referee.set_logger(Box::new(logger.clone()));
+
+        // bob passes, jill is kicked for failing to take a turn, so bob wins the tie
+        let players: Vec<Box<dyn PlayerApi>> = vec![
+            Box::new(MockPlayer::default()),
+            Box::new(BadPlayer::new(
+                Box::new(LocalPlayer::new(
+                    Name::from_static("jill"),
+                    NaiveStrategy::Euclid,
+                )),
+                BadFM::TakeTurn,
+            )),
+        ];
+        referee.run_game(players, vec![]);
+
+        assert_eq!(
+            *logger.events.lock(),
+            vec!["pass bob", "kick jill", "winner bob"]
+        );
+    }
+
+    #[test]
+    fn test_game_start() {
+        let mocks = [MockPlayer::default(), MockPlayer::default()];
+        let players = vec![
+            Player::new(
+                Box::new(mocks[0].clone()),
+                FullPlayerInfo::new((1, 1), (1, 1), (5, 5), ColorName::Red.into()),
+            ),
+            Player::new(
+                Box::new(mocks[1].clone()),
+                FullPlayerInfo::new((3, 3), (3, 3), (1, 5), ColorName::Blue.into()),
+            ),
+        ];
+        let mut state = State {
+            player_info: players.into(),
+            ..Default::default()
+        };
+
+        Referee::new(0).run_from_state(&mut state, &mut vec![], VecDeque::new());
+        for mock in mocks {
+            assert_eq!(*mock.game_starts.lock(), vec![(2, 0)]);
+            assert!(*mock.turns_taken.lock() > 0);
+        }
+    }
+
+    #[test]
+    fn test_observers_see_players_kicked_in_setup() {
+        #[derive(Debug, Default, Clone)]
+        struct RecordingObserver {
+            states: Arc<Mutex<Vec<State<FullPlayerInfo>>>>,
+        }
+
+        impl Observer for RecordingObserver {
+            fn recieve_state(&mut self, state: State<FullPlayerInfo>) {
+                self.states.lock().push(state);
+            }
+
+            fn game_over(&mut self) {}
+        }
+
+        let players = vec![
+            Player::new(
+                Box::new(MockPlayer::default()),
+                FullPlayerInfo::new((1, 1), (1, 1), (5, 5), ColorName::Red.into()),
+            ),
+            Player::new(
+                Box::new(BadPlayer::new(
+                    Box::new(LocalPlayer::new(
+                        Name::from_static("jill"),
+                        NaiveStrategy::Euclid,
+                    )),
+                    BadFM::SetUp,
+                )),
+                FullPlayerInfo::new((3, 3), (3, 3), (1, 5), ColorName::Blue.into()),
+            ),
+        ];
+        let mut state = State {
+            player_info: players.into(),
+            ..Default::default()
+        };
+
+        let observer = RecordingObserver::default();
+        let mut referee = Referee::new(0);
+        let GameResult { kicked, .. } = referee.run_from_state(
+            &mut state,
+            &mut vec![Box::new(observer.clone())],
+            VecDeque::new(),
+        );
+        assert_eq!(kicked.len(), 1);
+        assert_eq!(kicked[0].name(), Name::from_static("jill"));
+
+        let states = observer.states.lock();
+        let colors = |state: &State<FullPlayerInfo>| {
+            state
+                .player_info
+                .iter()
+                .map(|pi| pi.color())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            colors(&states[0]),
+            vec![Color::from(ColorName::Red), Color::from(ColorName::Blue)]
+        );
+        assert_eq!(colors(&states[1]), vec![Color::from(ColorName::Red)]);
+    }
+
+    #[test]
+    fn test_broadcast_on_change() {
+        #[derive(Debug, Default, Clone)]
+        struct CountingObserver {
+            states: Arc<Mutex<usize>>,
+        }
+
+        impl Observer for CountingObserver {
+            fn recieve_state(&mut self, _state: State<FullPlayerInfo>) {
+                *self.states.lock() += 1;
+            }
+
+            fn game_over(&mut self) {}
+        }
+
+        for broadcast_on_change in [false, true] {
+            let bob = MockPlayer::default();
+            let jill = MockPlayer::default();
+            let mut state = State {
+                player_info: VecDeque::from([
+                    Player::new(
+                        Box::new(bob.clone()),
+                        FullPlayerInfo::new((1, 1), (1, 1), (5, 5), ColorName::Red.into()),
+                    ),
+                    Player::new(
+                        Box::new(jill.clone()),
+                        FullPlayerInfo::new((3, 3), (3, 3), (1, 5), ColorName::Blue.into()),
+                    ),
+                ]),
+                ..Default::default()
+            };
+
+            let observer = CountingObserver::default();
+            let mut referee = Referee::with_config(
+                0,
+                Config {
+                    broadcast_on_change,
+                    ..Default::default()
+                },
+            );
+            referee.run_from_state(
+                &mut state,
+                &mut vec![Box::new(observer.clone())],
+                VecDeque::new(),
+            );
+
+            // both players pass in the only round
+            let turns = *bob.turns_taken.lock() + *jill.turns_taken.lock();
+            assert_eq!(turns, 2);
+            let broadcasts = *observer.states.lock();
+            if broadcast_on_change {
+                // only the initial state is sent
+                assert_eq!(broadcasts, 1);
+            } else {
+                assert_eq!(broadcasts, 3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_undo_slide_player_kicked() {
+        for cheater_first in [true, false] {
+            let logger = CapturingLogger::default();
+            let mut referee = Referee::new(0);
+            referee.set_logger(Box::new(logger.clone()));
+
+            let mut players: Vec<Box<dyn PlayerApi>> = vec![
+                Box::new(LocalPlayer::new(
+                    Name::from_static("joe"),
+                    NaiveStrategy::Euclid,
+                )),
+                Box::new(BadPlayer::new(
+                    Box::new(LocalPlayer::new(
+                        Name::from_static("jill"),
+                        NaiveStrategy::Euclid,
+                    )),
+                    BadFM::UndoSlide,
+                )),
+            ];
+            if cheater_first {
+                players.reverse();
+            }
+
+            let GameResult {
+                winners, kicked, ..
+            } = referee.run_game(players, vec![]);
+            assert_eq!(
+                kicked.iter().map(|pl| pl.name()).collect::<Vec<_>>(),
+                vec![Name::from_static("jill")]
+            );
+            assert_eq!(
+                winners.iter().map(|pl| pl.name()).collect::<Vec<_>>(),
+                vec![Name::from_static("joe")]
+            );
+            // jill is kicked before joe takes a second turn
+            assert_eq!(logger.events.lock()[0], "kick jill");
+        }
+    }
+
+    #[test]
+    fn test_undo_slide_loop_player() {
+        let mut jill = BadPlayerLoop::new(
+            Box::new(LocalPlayer::new(
+                Name::from_static("jill"),
+                NaiveStrategy::Euclid,
+            )),
+            BadFM::UndoSlide,
+            2,
+        );
+        let mut state: State<FullPlayerInfo> = State::default();
+        state.add_player(FullPlayerInfo::new(
+            (1, 1),
+            (1, 1),
+            (5, 5),
+            ColorName::Red.into(),
+        ));
+        state.previous_slide = Some(Slide::new_unchecked(0, CompassDirection::East));
+        let state: State<PlayerInfo> = state.into();
+        jill.setup(Some(state.clone()), (5, 5)).unwrap();
+
+        // jill plays by the rules until the second turn, which undoes the previous slide
+        let is_legal = |action: PlayerAction| {
+            let PlayerMove {
+                slide,
+                rotations,
+                destination,
+            } = action.unwrap();
+            state.is_valid_move(slide, rotations, destination)
+        };
+        assert!(is_legal(jill.take_turn(state.clone()).unwrap()));
+        let cheat = jill.take_turn(state.clone()).unwrap();
+        assert_eq!(
+            cheat.unwrap().slide,
+            Slide::new_unchecked(0, CompassDirection::West)
+        );
+        assert!(!is_legal(cheat));
+    }
+
+    #[test]
+    fn test_all_players_kicked() {
+        let cheater = |name| -> Box<dyn PlayerApi> {
+            Box::new(BadPlayer::new(
+                Box::new(LocalPlayer::new(
+                    Name::from_static(name),
+                    NaiveStrategy::Euclid,
+                )),
+                BadFM::UndoSlide,
+            ))
+        };
+        let failer = |name| -> Box<dyn PlayerApi> {
+            Box::new(BadPlayer::new(
+                Box::new(LocalPlayer::new(
+                    Name::from_static(name),
+                    NaiveStrategy::Euclid,
+                )),
+                BadFM::TakeTurn,
+            ))
+        };
+
+        for players in [
+            vec![cheater("jill"), cheater("joe")],
+            vec![cheater("jill"), failer("joe")],
+            vec![failer("jill"), failer("joe")],
+        ] {
+            let GameResult {
+                winners, kicked, ..
+            } = Referee::new(0).run_game(players, vec![]);
+            assert!(winners.is_empty());
+            assert_eq!(
+                kicked.iter().map(|pl| pl.name()).collect::<Vec<_>>(),
+                vec![Name::from_static("jill"), Name::from_static("joe")]
+            );
+        }
+    }
+
+    #[test]
+    fn test_referee_logger_distance() {
+        #[derive(Debug, Default, Clone)]
+        struct DistanceLogger {
+            distances: Arc<Mutex<Vec<(Name, usize)>>>,
+        }
+
+        impl RefereeLogger for DistanceLogger {
+            fn log_distance(&mut self, name: &Name, distance: usize) {
+                self.distances.lock().push((name.clone(), distance));
+            }
+        }
+
+        let logger = DistanceLogger::default();
+        let mut referee = Referee::new(0);
+        referee.set_logger(Box::new(logger.clone()));
+
+        let player = MockPlayer::default();
+        referee.run_game(vec![Box::new(player.clone())], vec![]);
+
+        // bob never moves off of his home, so his distance is measured from there
+        let home = player.state.lock().as_ref().unwrap().player_info[0].home();
+        let goal = player.goal.lock().unwrap();
+        assert_eq!(
+            *logger.distances.lock(),
+            vec![(
+                Name::from_static("bob"),
+                squared_euclidian_distance(&home, &goal)
+            )]
+        );
+    }
+
+    #[test]
+    fn test_observer_should_continue() {
+        #[derive(Debug, Default, Clone)]
+        struct SteppingObserver {
+            rounds: Arc<Mutex<usize>>,
+            game_over: Arc<Mutex<bool>>,
+        }
+
+        impl Observer for SteppingObserver {
+            fn recieve_state(&mut self, _state: State<FullPlayerInfo>) {}
+
+            fn game_over(&mut self) {
+                *self.game_over.lock() = true;
+            }
+
+            fn should_continue(&mut self) -> bool {
+                *self.rounds.lock() += 1;
+                false
+            }
+        }
+
+        // a player that always moves never ties the game by passing
+        let players = || -> Vec<Player> {
+            vec![Player::new(
+                Box::new(LocalPlayer::new(
+                    Name::from_static("joe"),
+                    NaiveStrategy::Euclid,
+                )),
+                FullPlayerInfo::new((1, 1), (1, 1), (5, 5), ColorName::Red.into()),
+            )]
+        };
+
+        let mut referee = Referee::new(0);
+        let observer = SteppingObserver::default();
+        let mut state = State {
+            player_info: players().into(),
+            ..Default::default()
+        };
+        referee.run_from_state(
+            &mut state,
+            &mut vec![Box::new(observer.clone())],
+            VecDeque::new(),
+        );
+        assert_eq!(*observer.rounds.lock(), 1);
+        assert!(*observer.game_over.lock());
+        // only a game that ends with `GameStatus::Stopped` leaves a snapshot behind
+        assert!(referee.stopped_game().is_some());
+
+        #[derive(Debug, Default, Clone)]
+        struct CountingObserver {
+            states: Arc<Mutex<usize>>,
+        }
+
+        impl Observer for CountingObserver {
+            fn recieve_state(&mut self, _state: State<FullPlayerInfo>) {
+                *self.states.lock() += 1;
+            }
+
+            fn game_over(&mut self) {}
+        }
+
+        // observers that don't override `should_continue` never stop the game
+        let observer = CountingObserver::default();
+        let mut state = State {
+            player_info: players().into(),
+            ..Default::default()
+        };
+        referee.run_from_state(
+            &mut state,
+            &mut vec![Box::new(observer.clone())],
+            VecDeque::new(),
+        );
+        assert!(referee.stopped_game().is_none());
+        // the initial state, and one state for each of joe's turns, so more than one round ran
+        assert!(*observer.states.lock() > 2);
+    }
+
+    #[test]
+    fn test_run_many_parallel() {
+        let rosters = || -> Vec<Vec<Box<dyn PlayerApi>>> {
+            (0..8)
+                .map(|_| -> Vec<Box<dyn PlayerApi>> {
+                    vec![
+                        Box::new(LocalPlayer::new(
+                            Name::from_static("joe"),
+                            NaiveStrategy::Euclid,
+                        )),
+                        Box::new(LocalPlayer::new(
+                            Name::from_static("bob"),
+                            NaiveStrategy::Riemann,
+                        )),
+                    ]
+                })
+                .collect()
+        };
+        let names = |result: GameResult| -> (Vec<Name>, Vec<Name>) {
+            (
+                result.winners.iter().map(|pl| pl.name()).collect(),
+                result.kicked.iter().map(|pl| pl.name()).collect(),
+            )
+        };
+
+        let parallel = run_many_parallel(rosters(), 10);
+        let sequential = rosters()
+            .into_iter()
+            .enumerate()
+            .map(|(idx, players)| Referee::new(10 + idx as u64).run_game(players, vec![]));
+        assert_eq!(parallel.len(), 8);
+        for (parallel, sequential) in parallel.into_iter().zip(sequential) {
+            assert_eq!(names(parallel), names(sequential));
+        }
+    }
+
+    #[test]
+    fn test_turn_times() {
+        #[derive(Debug, Default, Clone)]
+        struct SleepyPlayer(MockPlayer);
+
+        impl PlayerApi for SleepyPlayer {
+            fn name(&self) -> Name {
+                Name::from_static("sleepy")
+            }
+
+            fn propose_board0(&self, cols: u32, rows: u32) -> PlayerApiResult<Board> {
+                self.0.propose_board0(cols, rows)
+            }
+
+            fn setup(
+                &mut self,
+                state: Option<State<PlayerInfo>>,
+                goal: Position,
+            ) -> PlayerApiResult<()> {
+                self.0.setup(state, goal)
+            }
+
+            fn take_turn(&self, state: State<PlayerInfo>) -> PlayerApiResult<PlayerAction> {
+                thread::sleep(Duration::from_millis(20));
+                self.0.take_turn(state)
+            }
+
+            fn won(&mut self, did_win: bool) -> PlayerApiResult<()> {
+                self.0.won(did_win)
+            }
+        }
+
+        // the sleepy player passes, so the game ends after a single turn
+        let mut referee = Referee::with_config(
+            0,
+            Config {
+                time_turns: true,
+                ..Default::default()
+            },
+        );
+        let result = referee.run_game(vec![Box::new(SleepyPlayer::default())], vec![]);
+        let times = result.turn_times[&Name::from_static("sleepy")];
+        assert_eq!(times.turns, 1);
+        assert!(times.total >= Duration::from_millis(20));
+        assert!(times.average() >= Duration::from_millis(20));
+
+        let mut referee = Referee::new(0);
+        let result = referee.run_game(vec![Box::new(SleepyPlayer::default())], vec![]);
+        assert!(result.turn_times.is_empty());
+    }
+
     #[test]
     fn test_run_game() {
         let mut referee = Referee {
             rand: Box::new(ChaChaRng::seed_from_u64(1)),
-            multiple_goals: false,
+            config: Config {
+                multiple_goals: false,
+                ..Default::default()
+            },
+            logger: Box::new(NoLogger),
+            home_policy: None,
+            last_broadcast: None,
+            stopped_game: None,
         };
 
         let player = Box::new(MockPlayer::default());
         let players: Vec<Box<dyn PlayerApi>> = vec![player.clone()];
-        let GameResult { winners, kicked } = referee.run_game(players, vec![]);
+        let GameResult {
+            winners, kicked, ..
+        } = referee.run_game(players, vec![]);
         assert_eq!(winners[0].name(), player.name());
         assert_eq!(*player.turns_taken.lock(), 1);
         assert!(kicked.is_empty());
@@ -811,7 +2290,9 @@ mod tests {
             )),
             player,
         ];
-        let GameResult { winners, kicked } = referee.run_game(players, vec![]);
+        let GameResult {
+            winners, kicked, ..
+        } = referee.run_game(players, vec![]);
         assert_eq!(winners[0].name(), Name::from_static("joe"));
         assert_eq!(winners.len(), 1);
         assert!(kicked.is_empty());
@@ -830,13 +2311,21 @@ mod tests {
         ];
         assert_eq!(
             players[0].propose_board0(7, 7).unwrap(),
-            referee.get_player_boards(&players)
+            referee.get_player_boards(
+                &[Player::from(Box::new(LocalPlayer::new(
+                    Name::from_static("jill"),
+                    NaiveStrategy::Riemann,
+                )) as Box<dyn PlayerApi>)],
+                &mut []
+            )
         );
         assert_eq!(
             players[0].propose_board0(7, 7).unwrap(),
             DefaultBoard::<7, 7>::default_board()
         );
-        let GameResult { winners, kicked } = referee.run_game(players, vec![]);
+        let GameResult {
+            winners, kicked, ..
+        } = referee.run_game(players, vec![]);
         assert_eq!(winners.len(), 1);
         assert_eq!(winners[0].name(), Name::from_static("jill"));
         assert!(kicked.is_empty());
@@ -846,7 +2335,14 @@ mod tests {
     fn test_run_from_state() {
         let mut referee = Referee {
             rand: Box::new(ChaChaRng::seed_from_u64(1)),
-            multiple_goals: false,
+            config: Config {
+                multiple_goals: false,
+                ..Default::default()
+            },
+            logger: Box::new(NoLogger),
+            home_policy: None,
+            last_broadcast: None,
+            stopped_game: None,
         };
         let players = vec![
             Player::new(
@@ -881,10 +2377,11 @@ mod tests {
             })
         }));
         state.board.spare.connector = corner;
-        state.previous_slide = state.board.new_slide(0, CompassDirection::West);
+        state.set_previous_slide(state.board.new_slide(0, CompassDirection::West));
 
-        let GameResult { winners, kicked } =
-            referee.run_from_state(&mut state, &mut vec![], VecDeque::default());
+        let GameResult {
+            winners, kicked, ..
+        } = referee.run_from_state(&mut state, &mut vec![], VecDeque::default());
         assert_eq!(winners.len(), 2);
         assert_eq!(kicked.len(), 0);
     }
@@ -893,7 +2390,14 @@ mod tests {
     fn test_run_from_state_multiple_goals() {
         let mut referee = Referee {
             rand: Box::new(ChaChaRng::seed_from_u64(1)),
-            multiple_goals: true,
+            config: Config {
+                multiple_goals: true,
+                ..Default::default()
+            },
+            logger: Box::new(NoLogger),
+            home_policy: None,
+            last_broadcast: None,
+            stopped_game: None,
         };
         let players = vec![
             Player::new(
@@ -916,10 +2420,11 @@ mod tests {
             ..Default::default()
         };
         let goals = referee.get_initial_goals(&state);
-        let GameResult { winners, kicked } =
-            dbg!(referee.run_from_state(&mut state, &mut vec![], goals.into()));
+        let GameResult {
+            winners, kicked, ..
+        } = dbg!(referee.run_from_state(&mut state, &mut vec![], goals.into()));
         let (calculated_winners, losers) =
-            dbg!(Referee::calculate_winners(&state, GameStatus::Tie));
+            dbg!(Referee::new(0).calculate_winners(&state, GameStatus::Tie));
 
         assert_eq!(winners.len(), 1);
         assert_eq!(calculated_winners.len(), 1);
@@ -929,11 +2434,195 @@ mod tests {
         assert_eq!(losers[0].name(), "joe");
     }
 
+    #[test]
+    fn test_snapshot_resume() {
+        let mut red = FullPlayerInfo::new((1, 1), (1, 1), (5, 3), ColorName::Red.into());
+        red.collect_goal((3, 5));
+        let blue = FullPlayerInfo::new((5, 5), (3, 1), (3, 3), ColorName::Blue.into());
+        let snapshot = RefereeSnapshot {
+            state: State {
+                player_info: vec![red, blue].into(),
+                ..Default::default()
+            },
+            remaining_goals: VecDeque::from([(1, 5), (5, 1)]),
+            config: Config {
+                multiple_goals: true,
+                ..Default::default()
+            },
+        };
+        let players = || -> Vec<Box<dyn PlayerApi>> {
+            vec![
+                Box::new(LocalPlayer::new(
+                    Name::from_static("bob"),
+                    NaiveStrategy::Riemann,
+                )),
+                Box::new(LocalPlayer::new(
+                    Name::from_static("joe"),
+                    NaiveStrategy::Euclid,
+                )),
+            ]
+        };
+
+        let uninterrupted = Referee::new(0).resume(snapshot.clone(), players(), vec![]);
+
+        let saved = serde_json::to_string(&snapshot).unwrap();
+        let loaded: RefereeSnapshot = serde_json::from_str(&saved).unwrap();
+        assert_eq!(loaded.state, snapshot.state);
+        assert_eq!(loaded.remaining_goals, snapshot.remaining_goals);
+        assert!(loaded.config.multiple_goals);
+        let resumed = Referee::new(1).resume(loaded, players(), vec![]);
+
+        let outcome = |result: &GameResult| {
+            (
+                result
+                    .winners
+                    .iter()
+                    .map(|pl| (pl.name(), pl.info.clone()))
+                    .collect::<Vec<_>>(),
+                result.kicked.iter().map(|pl| pl.name()).collect::<Vec<_>>(),
+            )
+        };
+        assert!(!uninterrupted.winners.is_empty());
+        assert_eq!(outcome(&uninterrupted), outcome(&resumed));
+
+        // a game stopped by an observer after the first round resumes as if it never stopped
+        struct StoppingObserver;
+
+        impl Observer for StoppingObserver {
+            fn recieve_state(&mut self, _state: State<FullPlayerInfo>) {}
+
+            fn game_over(&mut self) {}
+
+            fn should_continue(&mut self) -> bool {
+                false
+            }
+        }
+
+        let mut referee = Referee::with_config(0, snapshot.config.clone());
+        let mut state = State {
+            board: snapshot.state.board.clone(),
+            player_info: players()
+                .into_iter()
+                .zip(snapshot.state.player_info.clone())
+                .map(|(api, info)| Player::new(api, info))
+                .collect(),
+            previous_slide: snapshot.state.previous_slide,
+        };
+        referee.run_from_state(
+            &mut state,
+            &mut vec![Box::new(StoppingObserver)],
+            snapshot.remaining_goals.clone(),
+        );
+        let stopped = referee.stopped_game().unwrap().clone();
+        assert_ne!(stopped.state, snapshot.state);
+
+        let saved = serde_json::to_string(&stopped).unwrap();
+        let loaded: RefereeSnapshot = serde_json::from_str(&saved).unwrap();
+        assert_eq!(loaded.state, stopped.state);
+        let resumed = Referee::new(2).resume(loaded, players(), vec![]);
+        assert_eq!(outcome(&uninterrupted), outcome(&resumed));
+
+        // games that are not stopped leave no snapshot behind
+        let mut state = State {
+            board: snapshot.state.board.clone(),
+            player_info: players()
+                .into_iter()
+                .zip(snapshot.state.player_info.clone())
+                .map(|(api, info)| Player::new(api, info))
+                .collect(),
+            previous_slide: None,
+        };
+        referee.run_from_state(&mut state, &mut vec![], snapshot.remaining_goals.clone());
+        assert!(referee.stopped_game().is_none());
+    }
+
+    #[test]
+    fn test_outcome_hash() {
+        let mut bob_info = FullPlayerInfo::new((1, 1), (1, 1), (1, 1), ColorName::Red.into());
+        bob_info.inc_goals_reached();
+        let bob = Player::new(Box::new(MockPlayer::default()), bob_info);
+        let jill = Player::new(
+            Box::new(LocalPlayer::new(
+                Name::from_static("jill"),
+                NaiveStrategy::Euclid,
+            )),
+            FullPlayerInfo::new((3, 3), (2, 2), (5, 5), ColorName::Blue.into()),
+        );
+        let result = GameResult {
+            winners: vec![bob.clone()],
+            kicked: vec![jill.clone()],
+            ..Default::default()
+        };
+        assert_eq!(result.outcome_hash(), 0xa267_bc91_1fd1_efc3);
+
+        let swapped = GameResult {
+            winners: vec![jill],
+            kicked: vec![bob],
+            ..Default::default()
+        };
+        assert_ne!(result.outcome_hash(), swapped.outcome_hash());
+
+        let players = || -> Vec<Box<dyn PlayerApi>> {
+            vec![
+                Box::new(LocalPlayer::new(
+                    Name::from_static("bob"),
+                    NaiveStrategy::Riemann,
+                )),
+                Box::new(LocalPlayer::new(
+                    Name::from_static("joe"),
+                    NaiveStrategy::Euclid,
+                )),
+            ]
+        };
+        assert_eq!(
+            Referee::new(7).run_game(players(), vec![]).outcome_hash(),
+            Referee::new(7).run_game(players(), vec![]).outcome_hash()
+        );
+    }
+
+    #[test]
+    fn test_preview_winners_matches_calculate_winners() {
+        let infos = [
+            ((1, 1), (5, 3), (5, 5), 1, ColorName::Red),
+            ((3, 3), (5, 5), (1, 5), 1, ColorName::Blue),
+            ((5, 5), (5, 1), (5, 3), 0, ColorName::Green),
+            ((1, 5), (3, 1), (3, 3), 1, ColorName::Yellow),
+        ];
+        let mut state: State<Player> = State::default();
+        for (home, position, goal, goals_reached, color) in infos {
+            let mut info = FullPlayerInfo::new(home, position, goal, color.into());
+            (0..goals_reached).for_each(|_| info.inc_goals_reached());
+            state.add_player(Player::new(Box::new(MockPlayer::default()), info));
+        }
+
+        let (winners, _) = Referee::new(0).calculate_winners(&state, GameStatus::Tie);
+        let preview = state.to_full_state().preview_winners();
+        assert_eq!(
+            preview,
+            winners.iter().map(|pl| pl.color()).collect::<Vec<_>>()
+        );
+        // red and yellow are both 4 away from their goals, blue is 16 away
+        assert_eq!(
+            preview,
+            vec![Color::from(ColorName::Red), Color::from(ColorName::Yellow)]
+        );
+        assert!(State::<FullPlayerInfo>::default()
+            .preview_winners()
+            .is_empty());
+    }
+
     #[test]
     fn test_process_move() {
-        let referee = Referee {
+        let mut referee = Referee {
             rand: Box::new(ChaChaRng::seed_from_u64(1)),
-            multiple_goals: false,
+            config: Config {
+                multiple_goals: false,
+                ..Default::default()
+            },
+            logger: Box::new(NoLogger),
+            home_policy: None,
+            last_broadcast: None,
+            stopped_game: None,
         };
         let players = vec![
             Player::new(
@@ -1052,6 +2741,134 @@ mod tests {
         assert_eq!(state.current_player_info(), &Color::from(ColorName::Green));
     }
 
+    #[test]
+    fn test_process_move_goal_then_home() {
+        let mut referee = Referee::new(0);
+        let mut num = 0;
+        let board = Board::from_connectors_str(&["┼┼┼┼┼┼┼"; 7].join("\n"), || {
+            num += 1;
+            Gem::pair_from_num(num)
+        })
+        .unwrap();
+        let mut state: State<Player> = State::new(
+            board,
+            vec![Player::new(
+                Box::new(LocalPlayer::new(
+                    Name::from_static("bob"),
+                    NaiveStrategy::Euclid,
+                )),
+                FullPlayerInfo::new((1, 1), (1, 3), (5, 5), ColorName::Red.into()),
+            )],
+        );
+        let move_to = |index, destination| PlayerMove {
+            slide: Slide::new_unchecked(index, CompassDirection::East),
+            rotations: 0,
+            destination,
+        };
+
+        // going home before reaching the goal does not win
+        let effect = referee.process_move(
+            &mut state,
+            &mut vec![],
+            &mut VecDeque::new(),
+            move_to(0, (1, 1)),
+        );
+        assert_eq!(effect, MoveEffect::Moved);
+        assert_eq!(state.current_player_info().goal(), (5, 5));
+        assert_eq!(state.current_player_info().get_goals_reached(), 0);
+
+        // reaching the goal does not win, but sends the player home
+        let effect = referee.process_move(
+            &mut state,
+            &mut vec![],
+            &mut VecDeque::new(),
+            move_to(2, (5, 5)),
+        );
+        assert_eq!(effect, MoveEffect::Moved);
+        assert_eq!(state.current_player_info().goal(), (1, 1));
+        assert_eq!(state.current_player_info().get_goals_reached(), 1);
+
+        // going home after reaching the goal wins
+        let effect = referee.process_move(
+            &mut state,
+            &mut vec![],
+            &mut VecDeque::new(),
+            move_to(4, (1, 1)),
+        );
+        assert_eq!(effect, MoveEffect::Won);
+        assert_eq!(state.current_player_info().position(), (1, 1));
+        assert_eq!(state.current_player_info().get_goals_reached(), 1);
+    }
+
+    #[test]
+    fn test_goal_is_now_home() {
+        #[derive(Debug, Default, Clone)]
+        struct HomeObserver {
+            colors: Arc<Mutex<Vec<Color>>>,
+        }
+
+        impl Observer for HomeObserver {
+            fn recieve_state(&mut self, _state: State<FullPlayerInfo>) {}
+
+            fn game_over(&mut self) {}
+
+            fn goal_is_now_home(&mut self, color: Color) {
+                self.colors.lock().push(color);
+            }
+        }
+
+        let mut referee = Referee::new(0);
+        let board = Board::from_connectors_str(&["┼┼┼┼┼┼┼"; 7].join("\n"), || {
+            Gem::pair_from_num(0)
+        })
+        .unwrap();
+        let mut state: State<Player> = State::new(
+            board,
+            vec![Player::new(
+                Box::new(MockPlayer::default()),
+                FullPlayerInfo::new((1, 1), (1, 3), (3, 3), ColorName::Red.into()),
+            )],
+        );
+        let move_to = |index, destination| PlayerMove {
+            slide: Slide::new_unchecked(index, CompassDirection::East),
+            rotations: 0,
+            destination,
+        };
+        let observer = HomeObserver::default();
+        let mut observers: Vec<Box<dyn Observer>> = vec![Box::new(observer.clone())];
+        let mut remaining_goals = VecDeque::from([(5, 5)]);
+
+        // reaching a goal when there are more to hand out does not send the player home
+        let effect = referee.process_move(
+            &mut state,
+            &mut observers,
+            &mut remaining_goals,
+            move_to(2, (3, 3)),
+        );
+        assert_eq!(effect, MoveEffect::Moved);
+        assert_eq!(state.current_player_info().goal(), (5, 5));
+        assert!(observer.colors.lock().is_empty());
+
+        let effect = referee.process_move(
+            &mut state,
+            &mut observers,
+            &mut remaining_goals,
+            move_to(4, (5, 5)),
+        );
+        assert_eq!(effect, MoveEffect::Moved);
+        assert_eq!(state.current_player_info().goal(), (1, 1));
+        assert_eq!(*observer.colors.lock(), vec![Color::from(ColorName::Red)]);
+
+        let effect = referee.process_move(
+            &mut state,
+            &mut observers,
+            &mut remaining_goals,
+            move_to(6, (1, 1)),
+        );
+        assert_eq!(effect, MoveEffect::Won);
+        assert_eq!(*observer.colors.lock(), vec![Color::from(ColorName::Red)]);
+    }
+
     #[test]
     fn next_player() {}
 
@@ -1059,7 +2876,14 @@ mod tests {
     fn test_run_round() {
         let mut referee = Referee {
             rand: Box::new(ChaChaRng::seed_from_u64(1)),
-            multiple_goals: false,
+            config: Config {
+                multiple_goals: false,
+                ..Default::default()
+            },
+            logger: Box::new(NoLogger),
+            home_policy: None,
+            last_broadcast: None,
+            stopped_game: None,
         };
         let players = vec![
             Player::new(
@@ -1092,7 +2916,8 @@ mod tests {
                 &mut state,
                 &mut vec![],
                 &mut kicked,
-                &mut VecDeque::default()
+                &mut VecDeque::default(),
+                &mut HashMap::new()
             )
             .is_none());
         assert_eq!(state.player_info[0].position(), (0, 0));
@@ -1106,7 +2931,8 @@ mod tests {
                 &mut state,
                 &mut vec![],
                 &mut kicked,
-                &mut VecDeque::default()
+                &mut VecDeque::default(),
+                &mut HashMap::new()
             )
             .is_some());
         // joe is now the 0th player because it won
@@ -1120,7 +2946,14 @@ mod tests {
     fn test_run_round_multiple() {
         let mut referee = Referee {
             rand: Box::new(ChaChaRng::seed_from_u64(1)),
-            multiple_goals: true,
+            config: Config {
+                multiple_goals: true,
+                ..Default::default()
+            },
+            logger: Box::new(NoLogger),
+            home_policy: None,
+            last_broadcast: None,
+            stopped_game: None,
         };
         let players = vec![
             Player::new(
@@ -1151,7 +2984,13 @@ mod tests {
         // the game does not end
         assert_eq!(remaining_goals.len(), 2);
         assert!(referee
-            .run_round(&mut state, &mut vec![], &mut kicked, &mut remaining_goals)
+            .run_round(
+                &mut state,
+                &mut vec![],
+                &mut kicked,
+                &mut remaining_goals,
+                &mut HashMap::new(),
+            )
             .is_none());
         assert_eq!(remaining_goals.len(), 1);
         assert_eq!(state.player_info[0].position(), (0, 0));
@@ -1161,7 +3000,13 @@ mod tests {
 
         // the game does not end
         assert!(referee
-            .run_round(&mut state, &mut vec![], &mut kicked, &mut remaining_goals)
+            .run_round(
+                &mut state,
+                &mut vec![],
+                &mut kicked,
+                &mut remaining_goals,
+                &mut HashMap::new(),
+            )
             .is_none());
         assert_eq!(remaining_goals.len(), 0);
         assert_eq!(state.player_info[0].position(), (5, 3));
@@ -1171,7 +3016,13 @@ mod tests {
 
         // the game does end
         assert!(referee
-            .run_round(&mut state, &mut vec![], &mut kicked, &mut remaining_goals)
+            .run_round(
+                &mut state,
+                &mut vec![],
+                &mut kicked,
+                &mut remaining_goals,
+                &mut HashMap::new(),
+            )
             .is_some());
         assert_eq!(remaining_goals.len(), 0);
         // joe is the first player bc it won
@@ -1,4 +1,3 @@
-use anyhow::anyhow;
 use common::{
     board::Board,
     grid::Position,
@@ -6,7 +5,7 @@ use common::{
     state::{PlayerInfo, State},
 };
 use players::{
-    player::{PlayerApi, PlayerApiError, PlayerApiResult},
+    player::{PlayerApi, PlayerApiError, PlayerApiResult, RemoteError},
     strategy::PlayerAction,
 };
 use serde::Deserialize;
@@ -29,6 +28,17 @@ pub struct PlayerProxy<In: Read + Send, Out: Write + Send> {
 
 const TIMEOUT: Duration = Duration::from_secs(4);
 
+/// Reads the name a remote player sends when it first connects from `reader`.
+///
+/// # Errors
+/// This will error if reading from `reader` fails, the name is not a Json string, or the name is
+/// not a valid `Name`.
+pub fn read_player_name(reader: impl Read) -> PlayerApiResult<Name> {
+    let raw = String::deserialize(&mut serde_json::Deserializer::from_reader(reader))
+        .map_err(RemoteError::Malformed)?;
+    Name::try_from(raw.as_str()).map_err(|_| RemoteError::InvalidName(raw).into())
+}
+
 impl PlayerProxy<TcpStream, TcpStream> {
     pub fn try_from_tcp(name: Name, stream: TcpStream) -> io::Result<Self> {
         stream
@@ -55,7 +65,25 @@ impl<In: Read + Send, Out: Write + Send> PlayerProxy<In, Out> {
     /// # Errors
     /// This will error if reading from the stream or deserializing the `JsonResult` fails
     fn read_result(&self) -> PlayerApiResult<JsonResult> {
-        Ok(JsonResult::deserialize(&mut *self.r#in.borrow_mut())?)
+        JsonResult::deserialize(&mut *self.r#in.borrow_mut()).map_err(|err| {
+            if err.is_eof() {
+                RemoteError::ConnectionClosed.into()
+            } else if err.is_io() {
+                let err = io::Error::from(err);
+                match err.kind() {
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+                        RemoteError::Timeout.into()
+                    }
+                    io::ErrorKind::UnexpectedEof
+                    | io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::BrokenPipe => RemoteError::ConnectionClosed.into(),
+                    _ => PlayerApiError::IoError(err),
+                }
+            } else {
+                RemoteError::Malformed(err).into()
+            }
+        })
     }
 
     /// Writes a `JsonFunctionCall` to `self.stream`
@@ -74,9 +102,10 @@ impl<In: Read + Send, Out: Write + Send> PlayerApi for PlayerProxy<In, Out> {
         self.name.clone()
     }
 
+    /// The spec doesn't say anything about calling `propose_board0` on `PlayerProxy`s, so remote
+    /// players never propose a `Board`.
     fn propose_board0(&self, _cols: u32, _rows: u32) -> PlayerApiResult<Board> {
-        // the spec doesn't say anything about calling propose_board0 on `PlayerProxy`s
-        todo!()
+        Err(RemoteError::Unsupported("propose_board0"))?
     }
 
     fn setup(&mut self, state: Option<State<PlayerInfo>>, goal: Position) -> PlayerApiResult<()> {
@@ -84,9 +113,7 @@ impl<In: Read + Send, Out: Write + Send> PlayerApi for PlayerProxy<In, Out> {
         self.send_function_call(&JsonFunctionCall::setup(state, goal))?;
         match self.read_result()? {
             JsonResult::Void => Ok(()),
-            _ => Err(PlayerApiError::Other(anyhow!(
-                "Got something other than \"void\", when calling `setup`!"
-            ))),
+            _ => Err(RemoteError::UnexpectedResult)?,
         }
     }
 
@@ -94,9 +121,7 @@ impl<In: Read + Send, Out: Write + Send> PlayerApi for PlayerProxy<In, Out> {
         self.send_function_call(&JsonFunctionCall::take_turn(state.clone()))?;
         match self.read_result()? {
             JsonResult::Choice(ch) => Ok(ch.try_into_action(&state.board)?),
-            _ => Err(PlayerApiError::Other(anyhow!(
-                "Got something other than a JsonChoice when calling `take_turn`!"
-            ))),
+            _ => Err(RemoteError::UnexpectedResult)?,
         }
     }
 
@@ -104,9 +129,7 @@ impl<In: Read + Send, Out: Write + Send> PlayerApi for PlayerProxy<In, Out> {
         self.send_function_call(&JsonFunctionCall::win(did_win))?;
         match self.read_result()? {
             JsonResult::Void => Ok(()),
-            _ => Err(PlayerApiError::Other(anyhow!(
-                "Got something other than \"void\" when calling `won`!"
-            ))),
+            _ => Err(RemoteError::UnexpectedResult)?,
         }
     }
 }
@@ -133,6 +156,37 @@ mod tests {
         }
     }
 
+    /// A reader that behaves like a `TcpStream` whose read timeout has elapsed
+    struct TimedOutReader;
+
+    impl Read for TimedOutReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::WouldBlock.into())
+        }
+    }
+
+    #[test]
+    fn test_read_player_name() {
+        assert_eq!(
+            read_player_name("\"john\"".as_bytes()).unwrap(),
+            Name::from_static("john")
+        );
+
+        let too_long = format!("\"{}\"", "a".repeat(30));
+        assert!(matches!(
+            read_player_name(too_long.as_bytes()),
+            Err(PlayerApiError::Remote(RemoteError::InvalidName(name))) if name == "a".repeat(30)
+        ));
+        assert!(matches!(
+            read_player_name("\"bad name!\"".as_bytes()),
+            Err(PlayerApiError::Remote(RemoteError::InvalidName(_)))
+        ));
+        assert!(matches!(
+            read_player_name("42".as_bytes()),
+            Err(PlayerApiError::Remote(RemoteError::Malformed(_)))
+        ));
+    }
+
     #[test]
     fn test_name() {
         let player = PlayerProxy::new(Name::from_static("john"), "".as_bytes(), Vec::new());
@@ -140,6 +194,20 @@ mod tests {
         assert_eq!(player.name(), Name::from_static("john"));
     }
 
+    #[test]
+    fn test_propose_board0() {
+        let player = PlayerProxy::new(Name::from_static("john"), "".as_bytes(), Vec::new());
+
+        assert!(matches!(
+            player.propose_board0(7, 7),
+            Err(PlayerApiError::Remote(RemoteError::Unsupported(
+                "propose_board0"
+            )))
+        ));
+        // nothing is sent to the remote player
+        assert!(player.get_output().is_empty());
+    }
+
     #[test]
     fn test_setup() {
         let mut player = PlayerProxy::new(Name::from_static("joe"), "\"void\"".as_bytes(), vec![]);
@@ -219,11 +287,24 @@ mod tests {
 
         // test no response
         let mut player = PlayerProxy::new(Name::from_static("joe"), "".as_bytes(), vec![]);
-        assert!(player.setup(None, (0, 0)).is_err());
+        assert!(matches!(
+            player.setup(None, (0, 0)),
+            Err(PlayerApiError::Remote(RemoteError::ConnectionClosed))
+        ));
 
         // test wrong response
         let mut player = PlayerProxy::new(Name::from_static("joe"), "wrong".as_bytes(), vec![]);
-        assert!(player.setup(None, (0, 0)).is_err());
+        assert!(matches!(
+            player.setup(None, (0, 0)),
+            Err(PlayerApiError::Remote(RemoteError::Malformed(_)))
+        ));
+
+        // test timeout
+        let mut player = PlayerProxy::new(Name::from_static("joe"), TimedOutReader, vec![]);
+        assert!(matches!(
+            player.setup(None, (0, 0)),
+            Err(PlayerApiError::Remote(RemoteError::Timeout))
+        ));
     }
 
     #[test]
@@ -264,11 +345,24 @@ mod tests {
 
         // test no response
         let player = PlayerProxy::new(Name::from_static("joe"), "".as_bytes(), vec![]);
-        assert!(player.take_turn(State::default()).is_err());
+        assert!(matches!(
+            player.take_turn(State::default()),
+            Err(PlayerApiError::Remote(RemoteError::ConnectionClosed))
+        ));
 
         // test wrong response
         let player = PlayerProxy::new(Name::from_static("joe"), "wrong".as_bytes(), vec![]);
-        assert!(player.take_turn(State::default()).is_err());
+        assert!(matches!(
+            player.take_turn(State::default()),
+            Err(PlayerApiError::Remote(RemoteError::Malformed(_)))
+        ));
+
+        // test response for a different method
+        let player = PlayerProxy::new(Name::from_static("joe"), "\"void\"".as_bytes(), vec![]);
+        assert!(matches!(
+            player.take_turn(State::default()),
+            Err(PlayerApiError::Remote(RemoteError::UnexpectedResult))
+        ));
     }
 
     #[test]
@@ -302,10 +396,16 @@ mod tests {
 
         // test no response
         let mut player = PlayerProxy::new(Name::from_static("joe"), "".as_bytes(), vec![]);
-        assert!(player.won(true).is_err());
+        assert!(matches!(
+            player.won(true),
+            Err(PlayerApiError::Remote(RemoteError::ConnectionClosed))
+        ));
 
         // test wrong response
         let mut player = PlayerProxy::new(Name::from_static("joe"), "wrong".as_bytes(), vec![]);
-        assert!(player.won(true).is_err());
+        assert!(matches!(
+            player.won(true),
+            Err(PlayerApiError::Remote(RemoteError::Malformed(_)))
+        ));
     }
 }
@@ -1,7 +1,6 @@
 use clap::Parser;
 use common::{
     grid::Position,
-    json::Name,
     state::{FullPlayerInfo, State},
 };
 use players::player::PlayerApi;
@@ -10,8 +9,7 @@ use referee::{
     player::Player,
     referee::{GameResult, Referee},
 };
-use remote::player::PlayerProxy;
-use serde::Deserialize;
+use remote::player::{read_player_name, PlayerProxy};
 use std::{
     io::stdin,
     net::{SocketAddr, TcpStream},
@@ -39,8 +37,7 @@ fn create_player(
         .set_read_timeout(Some(Duration::from_secs(2)))
         .expect("We did not pass a 0 for duration");
 
-    let name_stream = stream.try_clone()?;
-    let name = Name::deserialize(&mut serde_json::Deserializer::from_reader(name_stream))?;
+    let name = read_player_name(stream.try_clone()?)?;
 
     Ok(PlayerProxy::try_from_tcp(name, stream)?)
 }
@@ -52,9 +49,12 @@ async fn recieve_connections(
 ) {
     while connections.len() < num_players {
         if let Ok((stream, _)) = listener.accept().await {
-            if let Ok(player) = create_player(stream) {
-                connections.push(Box::new(player));
-                eprintln!("Player #{} connected", connections.len());
+            match create_player(stream) {
+                Ok(player) => {
+                    connections.push(Box::new(player));
+                    eprintln!("Player #{} connected", connections.len());
+                }
+                Err(err) => eprintln!("Rejected connection: {err}"),
             }
         };
     }
@@ -69,7 +69,7 @@ pub async fn main() -> anyhow::Result<()> {
         let jsonstate: JsonRefereeState = serde_json::from_reader(stdin())?;
         jsonstate.try_into()?
     };
-    let num_players = state_info.player_info.len();
+    let num_players = state_info.player_count();
 
     let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], port))).await?;
     eprintln!("Bound to port: {port}");